@@ -0,0 +1,24 @@
+#![no_main]
+
+use arb_alloy_consensus::transactions::batchpostingreport::decode_fields_sequencer;
+use arb_sequencer_network::sequencer::feed::BatchDataStats;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // The first 17 bytes select the ArbOS version and batch data stats.
+    let Some((header, payload)) = data.split_first_chunk::<17>() else {
+        return;
+    };
+    let arbos_version = if header[0] & 1 == 0 { 32 } else { 50 };
+    let length = u64::from_be_bytes(header[1..9].try_into().unwrap());
+    let non_zeros = u64::from_be_bytes(header[9..17].try_into().unwrap());
+    let stats = (header[0] & 2 == 0).then_some(BatchDataStats { length, non_zeros });
+    let legacy_gas = (header[0] & 4 == 0).then_some(length);
+
+    if let Ok(tx) =
+        decode_fields_sequencer(&mut &payload[..], 42161, arbos_version, stats, legacy_gas)
+    {
+        tx.decode_action()
+            .expect("batch posting report calldata should decode");
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use alloy_eips::{Decodable2718, Encodable2718};
+use arb_alloy_consensus::ArbTxEnvelope;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(tx) = ArbTxEnvelope::decode_2718(&mut &data[..]) else {
+        return;
+    };
+    let mut encoded = Vec::new();
+    tx.encode_2718(&mut encoded);
+    let decoded = ArbTxEnvelope::decode_2718(&mut encoded.as_slice())
+        .expect("re-encoded envelope should decode");
+    assert_eq!(decoded.hash(), tx.hash());
+    if let ArbTxEnvelope::Internal(internal) = &tx {
+        let _ = internal.decode_action();
+    }
+});
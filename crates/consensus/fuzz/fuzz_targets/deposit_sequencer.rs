@@ -0,0 +1,14 @@
+#![no_main]
+
+use alloy_primitives::{Address, B256, U256};
+use arb_alloy_consensus::transactions::TxDeposit;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = TxDeposit::decode_fields_sequencer(
+        &mut &data[..],
+        U256::from(42161),
+        B256::ZERO,
+        Address::ZERO,
+    );
+});
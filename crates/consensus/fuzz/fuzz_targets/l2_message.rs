@@ -0,0 +1,16 @@
+#![no_main]
+
+use alloy_primitives::{Address, B256, U256};
+use arb_alloy_consensus::L2Message;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = L2Message::decode(
+        &mut &data[..],
+        Address::ZERO,
+        Some(B256::ZERO),
+        U256::from(42161),
+    ) {
+        let _ = message.into_transactions();
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use alloy_primitives::{Address, B256, U256};
+use arb_alloy_consensus::transactions::submit_retryable::SubmitRetryableTx;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = SubmitRetryableTx::decode_fields_sequencer(
+        &mut &data[..],
+        U256::from(42161),
+        B256::ZERO,
+        Address::ZERO,
+        U256::ZERO,
+    ) {
+        let _ = tx.tx_hash();
+    }
+});
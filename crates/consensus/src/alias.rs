@@ -0,0 +1,45 @@
+use alloy_primitives::{Address, U160, uint};
+
+// Nitro reference
+// - util/arbmath: RemapL1Address() / InverseRemapL1Address()
+// - contracts/src/libraries/AddressAliasHelper.sol: applyL1ToL2Alias() / undoL1ToL2Alias()
+
+/// Offset added to L1 senders when their messages are delivered to L2.
+pub const L1_TO_L2_ALIAS_OFFSET: U160 = uint!(0x1111000000000000000000000000000000001111_U160);
+
+/// Returns the L2 alias of an L1 address, wrapping around at 2^160.
+pub fn apply_l1_to_l2_alias(l1_address: Address) -> Address {
+    let aliased = U160::from_be_bytes(l1_address.0.0).wrapping_add(L1_TO_L2_ALIAS_OFFSET);
+    Address::from(aliased.to_be_bytes())
+}
+
+/// Returns the L1 address an L2 alias was derived from.
+pub fn undo_l1_to_l2_alias(l2_address: Address) -> Address {
+    let original = U160::from_be_bytes(l2_address.0.0).wrapping_sub(L1_TO_L2_ALIAS_OFFSET);
+    Address::from(original.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn alias_round_trips_and_wraps() {
+        let l1 = address!("0x502fae7d46d88f08fc2f8ed27fcb2ab183eb3e1f");
+        let l2 = apply_l1_to_l2_alias(l1);
+        assert_eq!(l2, address!("0x6140ae7d46d88f08fc2f8ed27fcb2ab183eb4f30"));
+        assert_eq!(undo_l1_to_l2_alias(l2), l1);
+
+        let high = address!("0xffffffffffffffffffffffffffffffffffffffff");
+        assert_eq!(
+            apply_l1_to_l2_alias(high),
+            address!("0x1111000000000000000000000000000000001110")
+        );
+        assert_eq!(undo_l1_to_l2_alias(apply_l1_to_l2_alias(high)), high);
+        assert_eq!(
+            undo_l1_to_l2_alias(Address::ZERO),
+            address!("0xeeeeffffffffffffffffffffffffffffffffeeef")
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use alloc::boxed::Box;
+use alloy_consensus::{
+    Header, TxReceipt,
+    proofs::{calculate_receipt_root, ordered_trie_root_with_encoder},
+};
+use alloy_eips::Encodable2718;
+use alloy_primitives::{B256, Bloom, logs_bloom};
+use core::fmt;
+
+use crate::{ArbReceiptEnvelope, ArbTxEnvelope};
+
+// Nitro reference
+// - core/types/block.go: NewBlock() derives TxHash and ReceiptHash with DeriveSha() and the
+//   header Bloom from the receipts
+// - core/types/hashing.go: DeriveSha() keys the trie by RLP(index) and stores EncodeIndex()
+//   - typed transactions and receipts are stored with their type byte
+//   - classic (0x78) transactions are stored as their embedded legacy transaction
+//   - legacy and classic (0x78) receipts are stored untyped
+// - core/types/block.go: Header.Hash() is the keccak of the RLP encoded header
+
+/// Part of a block that did not match its header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockVerificationError {
+    /// The number of receipts differs from the number of transactions.
+    ReceiptCountMismatch {
+        /// Number of transactions in the block.
+        transactions: usize,
+        /// Number of receipts supplied.
+        receipts: usize,
+    },
+    /// The transactions root does not match the header.
+    TransactionsRoot {
+        /// Root committed in the header.
+        expected: B256,
+        /// Root computed from the transactions.
+        computed: B256,
+    },
+    /// The receipts root does not match the header.
+    ReceiptsRoot {
+        /// Root committed in the header.
+        expected: B256,
+        /// Root computed from the receipts.
+        computed: B256,
+    },
+    /// The logs bloom does not match the header.
+    LogsBloom {
+        /// Bloom committed in the header.
+        expected: Box<Bloom>,
+        /// Bloom computed from the receipt logs.
+        computed: Box<Bloom>,
+    },
+    /// The block hash does not match the header.
+    BlockHash {
+        /// Hash the block was returned with.
+        expected: B256,
+        /// Hash of the RLP encoded header.
+        computed: B256,
+    },
+}
+
+impl fmt::Display for BlockVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReceiptCountMismatch {
+                transactions,
+                receipts,
+            } => write!(
+                f,
+                "block has {transactions} transactions but {receipts} receipts"
+            ),
+            Self::TransactionsRoot { expected, computed } => write!(
+                f,
+                "transactions root mismatch: header has {expected}, computed {computed}"
+            ),
+            Self::ReceiptsRoot { expected, computed } => write!(
+                f,
+                "receipts root mismatch: header has {expected}, computed {computed}"
+            ),
+            Self::LogsBloom { .. } => write!(f, "logs bloom mismatch"),
+            Self::BlockHash { expected, computed } => write!(
+                f,
+                "block hash mismatch: expected {expected}, computed {computed}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BlockVerificationError {}
+
+/// Computes the transactions root of an L2 block.
+///
+/// Unlike [`alloy_consensus::proofs::calculate_transaction_root`], classic transactions are
+/// stored as their embedded legacy transaction rather than their typed encoding.
+pub fn calculate_transaction_root(transactions: &[ArbTxEnvelope]) -> B256 {
+    ordered_trie_root_with_encoder(transactions, |tx: &ArbTxEnvelope, buf| match tx {
+        ArbTxEnvelope::ArbitrumLegacy(tx) => tx.encode_only_legacy(buf),
+        tx => tx.encode_2718(buf),
+    })
+}
+
+/// Checks an L2 block against its header and the hash it was returned with.
+///
+/// Recomputes the transactions root, receipts root, logs bloom and header hash, returning
+/// the first part that does not match.
+pub fn verify_block(
+    header: &Header,
+    block_hash: B256,
+    transactions: &[ArbTxEnvelope],
+    receipts: &[ArbReceiptEnvelope],
+) -> Result<(), BlockVerificationError> {
+    if transactions.len() != receipts.len() {
+        return Err(BlockVerificationError::ReceiptCountMismatch {
+            transactions: transactions.len(),
+            receipts: receipts.len(),
+        });
+    }
+
+    let computed = calculate_transaction_root(transactions);
+    if computed != header.transactions_root {
+        return Err(BlockVerificationError::TransactionsRoot {
+            expected: header.transactions_root,
+            computed,
+        });
+    }
+
+    let computed = calculate_receipt_root(receipts);
+    if computed != header.receipts_root {
+        return Err(BlockVerificationError::ReceiptsRoot {
+            expected: header.receipts_root,
+            computed,
+        });
+    }
+
+    let computed = logs_bloom(receipts.iter().flat_map(TxReceipt::logs));
+    if computed != header.logs_bloom {
+        return Err(BlockVerificationError::LogsBloom {
+            expected: Box::new(header.logs_bloom),
+            computed: Box::new(computed),
+        });
+    }
+
+    let computed = header.hash_slow();
+    if computed != block_hash {
+        return Err(BlockVerificationError::BlockHash {
+            expected: block_hash,
+            computed,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArbHeaderInfo, ArbReceipt,
+        transactions::{TxArbitrumLegacy, TxDeposit},
+    };
+    use alloc::vec;
+    use alloy_consensus::{Eip658Value, Receipt, ReceiptWithBloom, Signed};
+    use alloy_primitives::{Address, Bytes, Log, LogData, TxKind, U256};
+
+    fn receipt(cumulative_gas_used: u64, logs: Vec<Log>) -> ReceiptWithBloom<ArbReceipt> {
+        ReceiptWithBloom {
+            logs_bloom: logs_bloom(&logs),
+            receipt: ArbReceipt::new(Receipt {
+                status: Eip658Value::Eip658(true),
+                cumulative_gas_used,
+                logs,
+            }),
+        }
+    }
+
+    #[test]
+    fn verifies_roots_bloom_and_hash() {
+        let classic = TxArbitrumLegacy {
+            nonce: 0,
+            gas_price: 1,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::repeat_byte(5)),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            v: U256::from(42161 * 2 + 35),
+            r: U256::from(1),
+            s: U256::from(1),
+            hash_override: B256::repeat_byte(6),
+            effective_gas_price: 1,
+            l1_block_number: 1,
+            override_sender: None,
+        };
+        let transactions = vec![
+            TxDeposit {
+                chain_id: U256::from(42161),
+                request_id: B256::repeat_byte(1),
+                from: Address::repeat_byte(2),
+                to: Address::repeat_byte(3),
+                value: U256::from(4),
+            }
+            .into(),
+            classic.clone().into(),
+        ];
+        // The trie holds the classic transaction exactly as the plain legacy transaction it
+        // embeds, so the standard root over that replacement is the expected one.
+        let (legacy, signature) = classic.as_signed_legacy().unwrap();
+        let mut as_legacy = transactions.clone();
+        as_legacy[1] = Signed::new_unchecked(legacy, signature, classic.hash_override).into();
+        let transactions_root = alloy_consensus::proofs::calculate_transaction_root(&as_legacy);
+        assert_eq!(calculate_transaction_root(&transactions), transactions_root);
+        assert_ne!(
+            alloy_consensus::proofs::calculate_transaction_root(&transactions),
+            transactions_root
+        );
+        let log = Log {
+            address: Address::repeat_byte(7),
+            data: LogData::new_unchecked(vec![B256::repeat_byte(8)], Bytes::new()),
+        };
+        let receipts = vec![
+            ArbReceiptEnvelope::Deposit(receipt(0, Vec::new())),
+            ArbReceiptEnvelope::ArbitrumLegacy(receipt(21_000, vec![log.clone()])),
+        ];
+
+        let mut header = Header {
+            number: 1,
+            transactions_root,
+            receipts_root: calculate_receipt_root(&receipts),
+            logs_bloom: logs_bloom([&log]),
+            ..Default::default()
+        };
+        ArbHeaderInfo {
+            arbos_format_version: 32,
+            ..Default::default()
+        }
+        .update_header(&mut header);
+        let hash = header.hash_slow();
+        assert_eq!(
+            verify_block(&header, hash, &transactions, &receipts),
+            Ok(())
+        );
+
+        assert!(matches!(
+            verify_block(&header, hash, &transactions[..1], &receipts[..1]),
+            Err(BlockVerificationError::TransactionsRoot { .. })
+        ));
+        let mut swapped = receipts.clone();
+        swapped.swap(0, 1);
+        assert!(matches!(
+            verify_block(&header, hash, &transactions, &swapped),
+            Err(BlockVerificationError::ReceiptsRoot { .. })
+        ));
+        assert_eq!(
+            verify_block(&header, hash, &transactions, &receipts[..1]),
+            Err(BlockVerificationError::ReceiptCountMismatch {
+                transactions: 2,
+                receipts: 1
+            })
+        );
+
+        let mut tampered = header.clone();
+        tampered.logs_bloom = Bloom::ZERO;
+        assert!(matches!(
+            verify_block(&tampered, hash, &transactions, &receipts),
+            Err(BlockVerificationError::LogsBloom { .. })
+        ));
+        tampered = header.clone();
+        tampered.gas_used = 1;
+        assert_eq!(
+            verify_block(&tampered, hash, &transactions, &receipts),
+            Err(BlockVerificationError::BlockHash {
+                expected: hash,
+                computed: tampered.hash_slow(),
+            })
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use alloy_consensus::Header;
-use alloy_primitives::{B256, Bytes};
+use alloy_primitives::{B256, Bytes, U256};
 use core::fmt;
 
 // Nitro reference
@@ -11,6 +11,9 @@ use core::fmt;
 // - internal/ethapi/api.go:
 //   - RPCMarshalHeader() returns extraData and mixHash as separate RPC fields
 //   - fillArbitrumNitroHeaderInfo() derives sendRoot/sendCount/l1BlockNumber from those fields
+// - arbos/block_processor.go: createNewHeader()
+//   - Difficulty is always 1
+//   - Nonce holds the number of delayed messages read, big-endian
 
 /// Exact byte length of Arbitrum's `Header.extra_data`.
 pub const ARB_HEADER_EXTRA_DATA_LEN: usize = 32;
@@ -194,6 +197,286 @@ impl ArbHeaderInfo {
     }
 }
 
+/// Error while wrapping a consensus header as an [`ArbHeader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArbHeaderError {
+    /// The Nitro fields could not be decoded.
+    Decode(ArbHeaderDecodeError),
+    /// The header difficulty is not 1.
+    InvalidDifficulty {
+        /// Difficulty present in the header.
+        got: U256,
+    },
+}
+
+impl fmt::Display for ArbHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(err) => err.fmt(f),
+            Self::InvalidDifficulty { got } => {
+                write!(
+                    f,
+                    "invalid Arbitrum header difficulty: got {got}, expected 1"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ArbHeaderError {}
+
+impl From<ArbHeaderDecodeError> for ArbHeaderError {
+    fn from(err: ArbHeaderDecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+/// Error while validating an Arbitrum header against its parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArbHeaderValidationError {
+    /// The header number does not follow the parent number.
+    NotChild {
+        /// Number of the parent header.
+        parent: u64,
+        /// Number of the child header.
+        number: u64,
+    },
+    /// The parent hash does not match the hash of the parent header.
+    ParentHashMismatch {
+        /// Number of the child header.
+        number: u64,
+        /// Hash of the parent header.
+        expected: B256,
+        /// Parent hash recorded in the child header.
+        got: B256,
+    },
+    /// The send count decreased.
+    SendCountDecreased {
+        /// Number of the child header.
+        number: u64,
+        /// Send count of the parent header.
+        parent: u64,
+        /// Send count of the child header.
+        child: u64,
+    },
+    /// The L1 block number decreased.
+    L1BlockNumberDecreased {
+        /// Number of the child header.
+        number: u64,
+        /// L1 block number of the parent header.
+        parent: u64,
+        /// L1 block number of the child header.
+        child: u64,
+    },
+    /// The number of delayed messages read decreased.
+    DelayedMessagesReadDecreased {
+        /// Number of the child header.
+        number: u64,
+        /// Delayed messages read by the parent header.
+        parent: u64,
+        /// Delayed messages read by the child header.
+        child: u64,
+    },
+    /// The ArbOS version decreased.
+    ArbosVersionDecreased {
+        /// Number of the child header.
+        number: u64,
+        /// ArbOS version of the parent header.
+        parent: u64,
+        /// ArbOS version of the child header.
+        child: u64,
+    },
+}
+
+impl fmt::Display for ArbHeaderValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotChild { parent, number } => {
+                write!(f, "header {number} does not follow parent {parent}")
+            }
+            Self::ParentHashMismatch {
+                number,
+                expected,
+                got,
+            } => write!(
+                f,
+                "header {number} has parent hash {got}, expected {expected}"
+            ),
+            Self::SendCountDecreased {
+                number,
+                parent,
+                child,
+            } => write!(
+                f,
+                "header {number} send count {child} is below parent send count {parent}"
+            ),
+            Self::L1BlockNumberDecreased {
+                number,
+                parent,
+                child,
+            } => write!(
+                f,
+                "header {number} L1 block number {child} is below parent L1 block number {parent}"
+            ),
+            Self::DelayedMessagesReadDecreased {
+                number,
+                parent,
+                child,
+            } => write!(
+                f,
+                "header {number} delayed messages read {child} is below parent count {parent}"
+            ),
+            Self::ArbosVersionDecreased {
+                number,
+                parent,
+                child,
+            } => write!(
+                f,
+                "header {number} ArbOS version {child} is below parent version {parent}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ArbHeaderValidationError {}
+
+/// Arbitrum block header with its decoded Nitro fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArbHeader {
+    header: Header,
+    info: ArbHeaderInfo,
+}
+
+impl ArbHeader {
+    /// Decodes the Nitro fields of `header`, checking its difficulty is 1.
+    pub fn new(header: Header) -> Result<Self, ArbHeaderError> {
+        let info = ArbHeaderInfo::decode_header(&header)?;
+        if header.difficulty != U256::from(1) {
+            return Err(ArbHeaderError::InvalidDifficulty {
+                got: header.difficulty,
+            });
+        }
+        Ok(Self { header, info })
+    }
+
+    /// Returns the consensus header.
+    pub const fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Consumes the wrapper, returning the consensus header.
+    pub fn into_header(self) -> Header {
+        self.header
+    }
+
+    /// Returns the decoded `extra_data` and `mix_hash` fields.
+    pub const fn info(&self) -> &ArbHeaderInfo {
+        &self.info
+    }
+
+    /// Returns the block number.
+    pub const fn number(&self) -> u64 {
+        self.header.number
+    }
+
+    /// Returns the Merkle root of the send queue.
+    pub const fn send_root(&self) -> B256 {
+        self.info.send_root
+    }
+
+    /// Returns the number of sends included so far.
+    pub const fn send_count(&self) -> u64 {
+        self.info.send_count
+    }
+
+    /// Returns the L1 block number observed by ArbOS.
+    pub const fn l1_block_number(&self) -> u64 {
+        self.info.l1_block_number
+    }
+
+    /// Returns the ArbOS version the block was produced with.
+    pub const fn arbos_version(&self) -> u64 {
+        self.info.arbos_format_version
+    }
+
+    /// Returns the number of delayed messages read, stored in `Header.nonce`.
+    pub const fn delayed_messages_read(&self) -> u64 {
+        u64::from_be_bytes(self.header.nonce.0)
+    }
+
+    /// Checks this header follows `parent`.
+    ///
+    /// The number and parent hash must link to `parent`. The send count, L1 block number,
+    /// delayed messages read and ArbOS version must not decrease.
+    pub fn validate_child(&self, parent: &Self) -> Result<(), ArbHeaderValidationError> {
+        let number = self.number();
+        if parent.number().checked_add(1) != Some(number) {
+            return Err(ArbHeaderValidationError::NotChild {
+                parent: parent.number(),
+                number,
+            });
+        }
+        let expected = parent.header.hash_slow();
+        if self.header.parent_hash != expected {
+            return Err(ArbHeaderValidationError::ParentHashMismatch {
+                number,
+                expected,
+                got: self.header.parent_hash,
+            });
+        }
+        if self.send_count() < parent.send_count() {
+            return Err(ArbHeaderValidationError::SendCountDecreased {
+                number,
+                parent: parent.send_count(),
+                child: self.send_count(),
+            });
+        }
+        if self.l1_block_number() < parent.l1_block_number() {
+            return Err(ArbHeaderValidationError::L1BlockNumberDecreased {
+                number,
+                parent: parent.l1_block_number(),
+                child: self.l1_block_number(),
+            });
+        }
+        if self.delayed_messages_read() < parent.delayed_messages_read() {
+            return Err(ArbHeaderValidationError::DelayedMessagesReadDecreased {
+                number,
+                parent: parent.delayed_messages_read(),
+                child: self.delayed_messages_read(),
+            });
+        }
+        if self.arbos_version() < parent.arbos_version() {
+            return Err(ArbHeaderValidationError::ArbosVersionDecreased {
+                number,
+                parent: parent.arbos_version(),
+                child: self.arbos_version(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks each header in `headers` follows the one before it.
+    pub fn validate_chain(headers: &[Self]) -> Result<(), ArbHeaderValidationError> {
+        headers
+            .windows(2)
+            .try_for_each(|pair| pair[1].validate_child(&pair[0]))
+    }
+}
+
+impl TryFrom<Header> for ArbHeader {
+    type Error = ArbHeaderError;
+
+    fn try_from(header: Header) -> Result<Self, Self::Error> {
+        Self::new(header)
+    }
+}
+
+impl AsRef<Header> for ArbHeader {
+    fn as_ref(&self) -> &Header {
+        &self.header
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -288,4 +571,127 @@ mod tests {
             8_888_888
         );
     }
+
+    fn arb_header(parent: Option<&ArbHeader>, info: ArbHeaderInfo, delayed: u64) -> ArbHeader {
+        let mut header = Header {
+            number: parent.map_or(0, |p| p.number() + 1),
+            parent_hash: parent.map_or(B256::ZERO, |p| p.header().hash_slow()),
+            difficulty: U256::from(1),
+            nonce: delayed.to_be_bytes().into(),
+            ..Default::default()
+        };
+        info.update_header(&mut header);
+        ArbHeader::new(header).unwrap()
+    }
+
+    #[test]
+    fn arb_header_validates_children() {
+        let info = ArbHeaderInfo {
+            send_root: B256::from([0x01; 32]),
+            send_count: 5,
+            l1_block_number: 100,
+            arbos_format_version: 31,
+        };
+        let genesis = arb_header(None, info, 3);
+        assert_eq!(genesis.delayed_messages_read(), 3);
+        assert_eq!(genesis.arbos_version(), 31);
+
+        let upgraded = ArbHeaderInfo {
+            send_count: 6,
+            arbos_format_version: 32,
+            ..info
+        };
+        let child = arb_header(Some(&genesis), upgraded, 3);
+        let grandchild = arb_header(Some(&child), upgraded, 4);
+        ArbHeader::validate_chain(&[genesis.clone(), child.clone(), grandchild.clone()]).unwrap();
+
+        assert_eq!(
+            ArbHeader::validate_chain(&[genesis.clone(), grandchild]),
+            Err(ArbHeaderValidationError::NotChild {
+                parent: 0,
+                number: 2
+            })
+        );
+        assert_eq!(
+            arb_header(Some(&child), info, 3).validate_child(&child),
+            Err(ArbHeaderValidationError::SendCountDecreased {
+                number: 2,
+                parent: 6,
+                child: 5
+            })
+        );
+        assert_eq!(
+            arb_header(Some(&child), upgraded, 2).validate_child(&child),
+            Err(ArbHeaderValidationError::DelayedMessagesReadDecreased {
+                number: 2,
+                parent: 3,
+                child: 2
+            })
+        );
+        assert!(matches!(
+            arb_header(Some(&genesis), upgraded, 3).validate_child(&child),
+            Err(ArbHeaderValidationError::NotChild { .. })
+        ));
+
+        let mut header = genesis.into_header();
+        header.difficulty = U256::ZERO;
+        assert_eq!(
+            ArbHeader::new(header),
+            Err(ArbHeaderError::InvalidDifficulty { got: U256::ZERO })
+        );
+        assert_eq!(
+            ArbHeader::new(Header::default()),
+            Err(ArbHeaderError::Decode(
+                ArbHeaderDecodeError::InvalidExtraDataLength { got: 0 }
+            ))
+        );
+    }
+
+    #[test]
+    fn arb_header_rejects_broken_links_and_downgrades() {
+        let info = ArbHeaderInfo {
+            send_root: B256::from([0x01; 32]),
+            send_count: 5,
+            l1_block_number: 100,
+            arbos_format_version: 31,
+        };
+        let parent = arb_header(None, info, 3);
+
+        let mut header = arb_header(Some(&parent), info, 3).into_header();
+        header.parent_hash = B256::repeat_byte(0xee);
+        assert_eq!(
+            ArbHeader::new(header).unwrap().validate_child(&parent),
+            Err(ArbHeaderValidationError::ParentHashMismatch {
+                number: 1,
+                expected: parent.header().hash_slow(),
+                got: B256::repeat_byte(0xee)
+            })
+        );
+
+        let earlier = ArbHeaderInfo {
+            l1_block_number: 99,
+            ..info
+        };
+        assert_eq!(
+            arb_header(Some(&parent), earlier, 3).validate_child(&parent),
+            Err(ArbHeaderValidationError::L1BlockNumberDecreased {
+                number: 1,
+                parent: 100,
+                child: 99
+            })
+        );
+
+        let downgraded = ArbHeaderInfo {
+            arbos_format_version: 30,
+            ..info
+        };
+        assert_eq!(
+            arb_header(Some(&parent), downgraded, 3).validate_child(&parent),
+            Err(ArbHeaderValidationError::ArbosVersionDecreased {
+                number: 1,
+                parent: 31,
+                child: 30
+            })
+        );
+    }
 }
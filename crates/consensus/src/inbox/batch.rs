@@ -0,0 +1,676 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use std::io::Read;
+
+use alloy_primitives::{Address, B256, Bytes, FixedBytes, U256, address};
+use alloy_rlp::Decodable;
+use arb_sequencer_network::sequencer::feed::{
+    Header, L1IncomingMessage, MessageType, MessageWithMetadata,
+};
+
+use crate::{inbox::zeroheavy, transactions::parse_l2::MAX_L2_MESSAGE_SIZE};
+
+// Nitro reference
+// - arbstate/inbox.go:
+//   - parseSequencerMessage() reads the 40-byte header, strips DA/zeroheavy/brotli layers and
+//     splits the payload into RLP-encoded segments
+//   - inboxMultiplexer.getNextMsg()/Pop() turn segments into messages, applying timestamp and
+//     L1 block deltas and clamping them to the header bounds
+// - daprovider/util.go: header flag bytes and DeserializeDASCertFrom()
+
+/// Poster of every L2 message decoded from a sequencer batch.
+pub const BATCH_POSTER_ADDRESS: Address = address!("0xa4b000000000000000000073657175656e636572");
+/// Length of the bounds header that opens every sequencer batch.
+pub const SEQUENCER_MESSAGE_HEADER_LEN: usize = 40;
+/// Maximum decompressed size of a batch payload.
+pub const MAX_DECOMPRESSED_LEN: usize = 16 * 1024 * 1024;
+/// Maximum number of segments read from one batch.
+pub const MAX_SEGMENTS_PER_BATCH: usize = 100 * 1024;
+
+/// Header byte of a brotli-compressed segment list.
+pub const BROTLI_MESSAGE_HEADER_BYTE: u8 = 0x00;
+/// Header flag of an AnyTrust DAS certificate.
+pub const DAS_MESSAGE_HEADER_FLAG: u8 = 0x80;
+/// Header flag of a DAS certificate using tree merkelization.
+pub const TREE_DAS_MESSAGE_HEADER_FLAG: u8 = 0x08;
+/// Header flag of a payload authenticated by the L1 inbox contract.
+pub const L1_AUTHENTICATED_MESSAGE_HEADER_FLAG: u8 = 0x40;
+/// Header flag of a zeroheavy-encoded payload.
+pub const ZEROHEAVY_MESSAGE_HEADER_FLAG: u8 = 0x20;
+/// Header byte of a payload posted as EIP-4844 blobs.
+pub const BLOB_HASHES_HEADER_FLAG: u8 = L1_AUTHENTICATED_MESSAGE_HEADER_FLAG | 0x10;
+
+const KNOWN_HEADER_BITS: u8 = DAS_MESSAGE_HEADER_FLAG
+    | TREE_DAS_MESSAGE_HEADER_FLAG
+    | L1_AUTHENTICATED_MESSAGE_HEADER_FLAG
+    | ZEROHEAVY_MESSAGE_HEADER_FLAG
+    | BLOB_HASHES_HEADER_FLAG
+    | BROTLI_MESSAGE_HEADER_BYTE;
+const MAX_ZEROHEAVY_DECOMPRESSED_LEN: usize = 101 * MAX_DECOMPRESSED_LEN / 100 + 64;
+
+const fn has_bits(byte: u8, bits: u8) -> bool {
+    byte & bits == bits
+}
+
+/// Segment kinds of a decompressed sequencer batch.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchSegmentKind {
+    /// Raw L2 message.
+    L2Message = 0,
+    /// Brotli-compressed L2 message.
+    L2MessageBrotli = 1,
+    /// Reads the next delayed inbox message.
+    DelayedMessages = 2,
+    /// Advances the timestamp of the following messages.
+    AdvanceTimestamp = 3,
+    /// Advances the L1 block number of the following messages.
+    AdvanceL1BlockNumber = 4,
+}
+
+impl BatchSegmentKind {
+    /// Converts a raw segment kind byte.
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::L2Message),
+            1 => Some(Self::L2MessageBrotli),
+            2 => Some(Self::DelayedMessages),
+            3 => Some(Self::AdvanceTimestamp),
+            4 => Some(Self::AdvanceL1BlockNumber),
+            _ => None,
+        }
+    }
+}
+
+/// Error while decoding a sequencer batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchDecodeError {
+    /// The batch is shorter than [`SEQUENCER_MESSAGE_HEADER_LEN`].
+    MissingHeader {
+        /// Batch length in bytes.
+        len: usize,
+    },
+    /// The payload has an L1-authenticated header byte this decoder does not know.
+    UnsupportedHeaderByte(u8),
+    /// The payload lives in blobs but no reader was supplied.
+    NoDataAvailabilityReader(u8),
+    /// The AnyTrust DAS certificate is truncated.
+    InvalidDasCertificate,
+    /// The blob payload is not a list of 32-byte versioned hashes.
+    InvalidBlobHashes {
+        /// Length of the hash list in bytes.
+        len: usize,
+    },
+    /// The data availability reader failed to recover the payload.
+    DataAvailability(String),
+}
+
+impl fmt::Display for BatchDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader { len } => {
+                write!(f, "sequencer batch of {len} bytes has no bounds header")
+            }
+            Self::UnsupportedHeaderByte(byte) => {
+                write!(f, "unsupported authenticated batch header byte {byte:#04x}")
+            }
+            Self::NoDataAvailabilityReader(byte) => {
+                write!(
+                    f,
+                    "batch header byte {byte:#04x} needs a data availability reader"
+                )
+            }
+            Self::InvalidDasCertificate => write!(f, "truncated DAS certificate"),
+            Self::InvalidBlobHashes { len } => {
+                write!(f, "blob hash list of {len} bytes is not a multiple of 32")
+            }
+            Self::DataAvailability(err) => {
+                write!(f, "failed to recover batch payload: {err}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for BatchDecodeError {}
+
+/// AnyTrust data availability certificate posted instead of the batch payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DasCertificate {
+    /// Header byte the certificate was posted with.
+    pub header: u8,
+    /// Hash of the committee keyset that signed the certificate.
+    pub keyset_hash: B256,
+    /// Hash of the certified payload.
+    pub data_hash: B256,
+    /// Unix time until which the committee stores the payload.
+    pub timeout: u64,
+    /// Certificate version; zero unless the tree flag is set.
+    pub version: u8,
+    /// Bitmask of the committee members that signed.
+    pub signers_mask: u64,
+    /// Aggregated BLS signature.
+    pub signature: FixedBytes<96>,
+}
+
+impl DasCertificate {
+    /// Decodes a certificate, starting at its header byte.
+    pub fn decode(mut buf: &[u8]) -> Result<Self, BatchDecodeError> {
+        fn take<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N], BatchDecodeError> {
+            let (data, rest) = buf
+                .split_first_chunk::<N>()
+                .ok_or(BatchDecodeError::InvalidDasCertificate)?;
+            *buf = rest;
+            Ok(*data)
+        }
+
+        let [header] = take::<1>(&mut buf)?;
+        if !has_bits(header, DAS_MESSAGE_HEADER_FLAG) {
+            return Err(BatchDecodeError::InvalidDasCertificate);
+        }
+        let keyset_hash = B256::from(take::<32>(&mut buf)?);
+        let data_hash = B256::from(take::<32>(&mut buf)?);
+        let timeout = u64::from_be_bytes(take(&mut buf)?);
+        let version = if has_bits(header, TREE_DAS_MESSAGE_HEADER_FLAG) {
+            take::<1>(&mut buf)?[0]
+        } else {
+            0
+        };
+        Ok(Self {
+            header,
+            keyset_hash,
+            data_hash,
+            timeout,
+            version,
+            signers_mask: u64::from_be_bytes(take(&mut buf)?),
+            signature: FixedBytes::from(take::<96>(&mut buf)?),
+        })
+    }
+}
+
+/// Source of batch payloads that are posted outside calldata.
+pub trait DataAvailabilityReader {
+    /// Returns the payload committed to by an AnyTrust certificate.
+    fn recover_anytrust_payload(&self, certificate: &DasCertificate) -> Result<Vec<u8>, String>;
+
+    /// Returns the payload carried by the blobs with these versioned hashes, already decoded
+    /// from blob field elements.
+    fn recover_blob_payload(&self, versioned_hashes: &[B256]) -> Result<Vec<u8>, String>;
+}
+
+/// Sequencer batch split into segments, with the bounds from its header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SequencerBatch {
+    /// Lowest timestamp a message in the batch may have.
+    pub min_timestamp: u64,
+    /// Highest timestamp a message in the batch may have.
+    pub max_timestamp: u64,
+    /// Lowest L1 block number a message in the batch may have.
+    pub min_l1_block: u64,
+    /// Highest L1 block number a message in the batch may have.
+    pub max_l1_block: u64,
+    /// Delayed messages read once the batch is fully processed.
+    pub after_delayed_messages: u64,
+    /// Segments of the batch, each starting with a [`BatchSegmentKind`] byte.
+    pub segments: Vec<Bytes>,
+}
+
+impl SequencerBatch {
+    /// Decodes batch data as posted to `SequencerInbox`.
+    ///
+    /// Payload errors Nitro tolerates (bad compression, unknown formats, malformed segments)
+    /// leave the segment list short or empty instead of failing. Blob batches need a `reader`;
+    /// DAS batches without one decode with no segments, as in Nitro.
+    pub fn decode(
+        data: &[u8],
+        reader: Option<&dyn DataAvailabilityReader>,
+    ) -> Result<Self, BatchDecodeError> {
+        let Some((header, payload)) = data.split_first_chunk::<SEQUENCER_MESSAGE_HEADER_LEN>()
+        else {
+            return Err(BatchDecodeError::MissingHeader { len: data.len() });
+        };
+        let word = |i: usize| u64::from_be_bytes(header[i * 8..i * 8 + 8].try_into().unwrap());
+        let mut batch = Self {
+            min_timestamp: word(0),
+            max_timestamp: word(1),
+            min_l1_block: word(2),
+            max_l1_block: word(3),
+            after_delayed_messages: word(4),
+            segments: Vec::new(),
+        };
+
+        let mut payload = payload.to_vec();
+        if let Some(&flag) = payload.first() {
+            if has_bits(flag, L1_AUTHENTICATED_MESSAGE_HEADER_FLAG)
+                && flag & !KNOWN_HEADER_BITS != 0
+            {
+                return Err(BatchDecodeError::UnsupportedHeaderByte(flag));
+            }
+            if has_bits(flag, DAS_MESSAGE_HEADER_FLAG) {
+                // Without a reader Nitro only logs an error and parses the certificate as the
+                // payload, which yields no segments. Delayed messages are still read past them.
+                if let Some(reader) = reader {
+                    let certificate = DasCertificate::decode(&payload)?;
+                    payload = reader
+                        .recover_anytrust_payload(&certificate)
+                        .map_err(BatchDecodeError::DataAvailability)?;
+                }
+            } else if has_bits(flag, BLOB_HASHES_HEADER_FLAG) {
+                let reader = reader.ok_or(BatchDecodeError::NoDataAvailabilityReader(flag))?;
+                let hashes = &payload[1..];
+                if hashes.len() % 32 != 0 {
+                    return Err(BatchDecodeError::InvalidBlobHashes { len: hashes.len() });
+                }
+                let hashes: Vec<B256> = hashes.chunks_exact(32).map(B256::from_slice).collect();
+                payload = reader
+                    .recover_blob_payload(&hashes)
+                    .map_err(BatchDecodeError::DataAvailability)?;
+            }
+        }
+
+        if payload
+            .first()
+            .is_some_and(|&flag| has_bits(flag, ZEROHEAVY_MESSAGE_HEADER_FLAG))
+        {
+            payload = zeroheavy::decode(&payload[1..], MAX_ZEROHEAVY_DECOMPRESSED_LEN);
+        }
+
+        if payload.first() == Some(&BROTLI_MESSAGE_HEADER_BYTE)
+            && let Some(decompressed) = decompress(&payload[1..], MAX_DECOMPRESSED_LEN)
+        {
+            let mut buf = &decompressed[..];
+            while !buf.is_empty() && batch.segments.len() < MAX_SEGMENTS_PER_BATCH {
+                let Ok(segment) = Bytes::decode(&mut buf) else {
+                    break;
+                };
+                batch.segments.push(segment);
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Returns the messages of the batch, given the delayed messages read before it.
+    pub const fn messages(&self, delayed_messages_read: u64) -> BatchMessages<'_> {
+        BatchMessages {
+            batch: self,
+            segment: 0,
+            timestamp: 0,
+            block_number: 0,
+            delayed_messages_read,
+            done: false,
+        }
+    }
+}
+
+/// Brotli-decompresses `data`, failing if the output exceeds `limit` bytes.
+fn decompress(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    brotli_decompressor::Decompressor::new(data, 4096)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut out)
+        .ok()?;
+    (out.len() <= limit).then_some(out)
+}
+
+/// Message produced by a sequencer batch.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum BatchMessage {
+    /// A message carried by the batch itself.
+    Message(MessageWithMetadata),
+    /// The delayed inbox message at `index`, which the caller reads from the delayed inbox.
+    Delayed {
+        /// Index of the delayed message.
+        index: u64,
+        /// Delayed messages read once this message is processed.
+        delayed_messages_read: u64,
+    },
+}
+
+impl BatchMessage {
+    /// Returns the message, reading delayed messages through `read_delayed`.
+    pub fn resolve<E>(
+        self,
+        read_delayed: impl FnOnce(u64) -> Result<L1IncomingMessage, E>,
+    ) -> Result<MessageWithMetadata, E> {
+        match self {
+            Self::Message(msg) => Ok(msg),
+            Self::Delayed {
+                index,
+                delayed_messages_read,
+            } => Ok(MessageWithMetadata {
+                l1_incoming_message: read_delayed(index)?,
+                delayed_messages_read,
+            }),
+        }
+    }
+}
+
+/// Iterator over the messages of a [`SequencerBatch`].
+#[derive(Clone, Debug)]
+pub struct BatchMessages<'a> {
+    batch: &'a SequencerBatch,
+    segment: usize,
+    timestamp: u64,
+    block_number: u64,
+    delayed_messages_read: u64,
+    done: bool,
+}
+
+impl BatchMessages<'_> {
+    /// Returns `true` once no segment after the current one produces a message.
+    fn is_last(&self) -> bool {
+        if self.delayed_messages_read < self.batch.after_delayed_messages {
+            return false;
+        }
+        !self
+            .batch
+            .segments
+            .iter()
+            .skip(self.segment + 1)
+            .any(|segment| {
+                segment.first().is_some_and(|&kind| {
+                    matches!(
+                        BatchSegmentKind::from_u8(kind),
+                        Some(
+                            BatchSegmentKind::L2Message
+                                | BatchSegmentKind::L2MessageBrotli
+                                | BatchSegmentKind::DelayedMessages
+                        )
+                    )
+                })
+            })
+    }
+
+    fn next_message(&mut self) -> Option<BatchMessage> {
+        let segments = &self.batch.segments;
+        while let Some(segment) = segments.get(self.segment) {
+            let kind = segment.first().copied().and_then(BatchSegmentKind::from_u8);
+            match kind {
+                Some(
+                    BatchSegmentKind::AdvanceTimestamp | BatchSegmentKind::AdvanceL1BlockNumber,
+                ) => {
+                    if let Ok(delta) = u64::decode(&mut &segment[1..]) {
+                        if kind == Some(BatchSegmentKind::AdvanceTimestamp) {
+                            self.timestamp = self.timestamp.wrapping_add(delta);
+                        } else {
+                            self.block_number = self.block_number.wrapping_add(delta);
+                        }
+                    }
+                    self.segment += 1;
+                }
+                _ if segment.is_empty() => self.segment += 1,
+                _ => break,
+            }
+        }
+        // Only the message sees the clamped values; later deltas apply to the raw ones.
+        let timestamp = self.timestamp.clamp(
+            self.batch.min_timestamp,
+            self.batch.max_timestamp.max(self.batch.min_timestamp),
+        );
+        let block_number = self.block_number.clamp(
+            self.batch.min_l1_block,
+            self.batch.max_l1_block.max(self.batch.min_l1_block),
+        );
+
+        // Past the last segment, missing delayed messages are read as virtual segments.
+        let (kind, body) = segments.get(self.segment).map_or(
+            (BatchSegmentKind::DelayedMessages as u8, &[][..]),
+            |segment| (segment[0], &segment[1..]),
+        );
+        match BatchSegmentKind::from_u8(kind)? {
+            BatchSegmentKind::L2Message => Some(self.l2_message(body, timestamp, block_number)),
+            BatchSegmentKind::L2MessageBrotli => decompress(body, MAX_L2_MESSAGE_SIZE)
+                .map(|body| self.l2_message(&body, timestamp, block_number)),
+            BatchSegmentKind::DelayedMessages => {
+                if self.delayed_messages_read >= self.batch.after_delayed_messages {
+                    return None;
+                }
+                let index = self.delayed_messages_read;
+                self.delayed_messages_read += 1;
+                Some(BatchMessage::Delayed {
+                    index,
+                    delayed_messages_read: self.delayed_messages_read,
+                })
+            }
+            BatchSegmentKind::AdvanceTimestamp | BatchSegmentKind::AdvanceL1BlockNumber => None,
+        }
+    }
+
+    fn l2_message(&self, l2msg: &[u8], timestamp: u64, block_number: u64) -> BatchMessage {
+        let header = Header::new(
+            MessageType::L2Message.to_u8(),
+            BATCH_POSTER_ADDRESS,
+            block_number,
+            timestamp,
+            None,
+            Some(U256::ZERO),
+        );
+        BatchMessage::Message(MessageWithMetadata {
+            l1_incoming_message: L1IncomingMessage::new(header, l2msg),
+            delayed_messages_read: self.delayed_messages_read,
+        })
+    }
+}
+
+impl Iterator for BatchMessages<'_> {
+    type Item = BatchMessage;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // Undecodable segments still produce a message, as Nitro's `InvalidL1Message`.
+        let msg = self.next_message().unwrap_or_else(|| {
+            let header = Header::new(
+                MessageType::Invalid.to_u8(),
+                Address::ZERO,
+                0,
+                0,
+                None,
+                Some(U256::ZERO),
+            );
+            BatchMessage::Message(MessageWithMetadata {
+                l1_incoming_message: L1IncomingMessage::new(header, &[]),
+                delayed_messages_read: self.batch.after_delayed_messages,
+            })
+        });
+        if self.is_last() {
+            self.done = true;
+        } else {
+            self.segment += 1;
+        }
+        Some(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use arb_sequencer_network::sequencer::feed::L1Header;
+    use std::io::Write;
+
+    fn brotli(data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut out, 4096, 11, 22);
+            writer.write_all(data).unwrap();
+        }
+        out
+    }
+
+    fn segment(kind: BatchSegmentKind, body: &[u8]) -> Vec<u8> {
+        let mut segment = vec![kind as u8];
+        segment.extend_from_slice(body);
+        segment
+    }
+
+    fn rlp_u64(value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        value.encode(&mut out);
+        out
+    }
+
+    fn batch_data(bounds: [u64; 5], segments: &[Vec<u8>], zeroheavy: bool) -> Vec<u8> {
+        let mut list = Vec::new();
+        for segment in segments {
+            segment[..].encode(&mut list);
+        }
+        let mut payload = vec![BROTLI_MESSAGE_HEADER_BYTE];
+        payload.extend(brotli(&list));
+        if zeroheavy {
+            let mut encoded = vec![ZEROHEAVY_MESSAGE_HEADER_FLAG];
+            encoded.extend(zeroheavy::encode(&payload));
+            payload = encoded;
+        }
+        let mut data: Vec<u8> = bounds.iter().flat_map(|word| word.to_be_bytes()).collect();
+        data.extend(payload);
+        data
+    }
+
+    fn header(msg: &BatchMessage) -> (u8, u64, u64, u64, Vec<u8>) {
+        let BatchMessage::Message(msg) = msg else {
+            panic!("expected a batch message, got {msg:?}");
+        };
+        let incoming = &msg.l1_incoming_message;
+        let header = L1Header::from_header(&incoming.header, msg.delayed_messages_read).unwrap();
+        (
+            header.kind,
+            header.timestamp,
+            header.block_number,
+            header.delayed_messages_read,
+            incoming.l2msg_bytes().unwrap(),
+        )
+    }
+
+    #[test]
+    fn batch_applies_deltas_and_interleaves_delayed_messages() {
+        let segments = [
+            segment(BatchSegmentKind::AdvanceTimestamp, &rlp_u64(1_000)),
+            segment(BatchSegmentKind::AdvanceL1BlockNumber, &rlp_u64(50)),
+            segment(BatchSegmentKind::L2Message, &[0x04, 0xaa]),
+            segment(BatchSegmentKind::DelayedMessages, &[]),
+            segment(BatchSegmentKind::AdvanceTimestamp, &rlp_u64(5)),
+            segment(BatchSegmentKind::L2MessageBrotli, &brotli(&[0x04, 0xbb])),
+        ];
+        for zeroheavy in [false, true] {
+            let data = batch_data([1_010, 2_000, 60, 70, 12], &segments, zeroheavy);
+            let batch = SequencerBatch::decode(&data, None).unwrap();
+            assert_eq!(batch.segments.len(), 6);
+            assert_eq!(batch.after_delayed_messages, 12);
+
+            let messages: Vec<_> = batch.messages(10).collect();
+            assert_eq!(messages.len(), 4);
+            // Timestamp 1000 is clamped up to the batch minimum.
+            assert_eq!(header(&messages[0]), (3, 1_010, 60, 10, vec![0x04, 0xaa]));
+            assert_eq!(
+                messages[1],
+                BatchMessage::Delayed {
+                    index: 10,
+                    delayed_messages_read: 11
+                }
+            );
+            // The raw timestamp 1005 is still below the minimum.
+            assert_eq!(header(&messages[2]), (3, 1_010, 60, 11, vec![0x04, 0xbb]));
+            // The remaining delayed message is read past the last segment.
+            assert_eq!(
+                messages[3],
+                BatchMessage::Delayed {
+                    index: 11,
+                    delayed_messages_read: 12
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn batch_rejects_unknown_or_unreadable_payloads() {
+        let mut data = vec![0u8; SEQUENCER_MESSAGE_HEADER_LEN];
+        assert_eq!(
+            SequencerBatch::decode(&data[..39], None),
+            Err(BatchDecodeError::MissingHeader { len: 39 })
+        );
+
+        data.push(0x41);
+        assert_eq!(
+            SequencerBatch::decode(&data, None),
+            Err(BatchDecodeError::UnsupportedHeaderByte(0x41))
+        );
+
+        *data.last_mut().unwrap() = BLOB_HASHES_HEADER_FLAG;
+        assert_eq!(
+            SequencerBatch::decode(&data, None),
+            Err(BatchDecodeError::NoDataAvailabilityReader(
+                BLOB_HASHES_HEADER_FLAG
+            ))
+        );
+
+        // Garbage after a brotli header yields no segments and a single invalid message.
+        *data.last_mut().unwrap() = BROTLI_MESSAGE_HEADER_BYTE;
+        data.extend([0xff; 8]);
+        let batch = SequencerBatch::decode(&data, None).unwrap();
+        assert!(batch.segments.is_empty());
+        let messages: Vec<_> = batch.messages(0).collect();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(header(&messages[0]).0, MessageType::Invalid.to_u8());
+    }
+
+    #[test]
+    fn das_batches_are_recovered_through_the_reader() {
+        struct Committee(Vec<u8>);
+
+        impl DataAvailabilityReader for Committee {
+            fn recover_anytrust_payload(
+                &self,
+                certificate: &DasCertificate,
+            ) -> Result<Vec<u8>, String> {
+                assert_eq!(certificate.version, 1);
+                assert_eq!(certificate.signers_mask, 0b101);
+                Ok(self.0.clone())
+            }
+
+            fn recover_blob_payload(&self, _: &[B256]) -> Result<Vec<u8>, String> {
+                Err("no blobs".into())
+            }
+        }
+
+        let inner = batch_data(
+            [0, u64::MAX, 0, u64::MAX, 0],
+            &[segment(BatchSegmentKind::L2Message, &[0x04])],
+            false,
+        );
+        let committee = Committee(inner[SEQUENCER_MESSAGE_HEADER_LEN..].to_vec());
+
+        let mut data = inner[..SEQUENCER_MESSAGE_HEADER_LEN].to_vec();
+        data.push(DAS_MESSAGE_HEADER_FLAG | TREE_DAS_MESSAGE_HEADER_FLAG);
+        data.extend([0x11; 32]);
+        data.extend([0x22; 32]);
+        data.extend(7u64.to_be_bytes());
+        data.push(1);
+        data.extend(0b101u64.to_be_bytes());
+        data.extend([0x33; 96]);
+
+        let certificate = DasCertificate::decode(&data[SEQUENCER_MESSAGE_HEADER_LEN..]).unwrap();
+        assert_eq!(certificate.data_hash, B256::repeat_byte(0x22));
+        assert_eq!(certificate.timeout, 7);
+
+        let batch = SequencerBatch::decode(&data, Some(&committee)).unwrap();
+        assert_eq!(batch.segments.len(), 1);
+
+        // Without a reader the batch has no segments but still reads its delayed messages.
+        data[32..40].copy_from_slice(&1u64.to_be_bytes());
+        let batch = SequencerBatch::decode(&data, None).unwrap();
+        assert!(batch.segments.is_empty());
+        assert_eq!(
+            batch.messages(0).collect::<Vec<_>>(),
+            [BatchMessage::Delayed {
+                index: 0,
+                delayed_messages_read: 1
+            }]
+        );
+        assert_eq!(
+            DasCertificate::decode(&data[SEQUENCER_MESSAGE_HEADER_LEN..data.len() - 1]),
+            Err(BatchDecodeError::InvalidDasCertificate)
+        );
+    }
+}
@@ -0,0 +1,9 @@
+/// `SequencerInbox` batch decoding into feed-style messages.
+#[cfg(feature = "std")]
+pub mod batch;
+/// Delayed inbox messages from L1 Bridge and Inbox logs.
+pub mod delayed;
+/// L2 transaction and retryable ticket prediction for L1 submissions.
+pub mod predict;
+/// Zeroheavy calldata encoding used by batch payloads.
+pub mod zeroheavy;
@@ -0,0 +1,300 @@
+use alloy_primitives::{Address, B256, Bytes, ChainId, TxKind, U256};
+
+use crate::transactions::{ArbTxEnvelope, TxDeposit, TxRetry, submit_retryable::SubmitRetryableTx};
+
+// Nitro reference
+// - arbos/parse_l2.go: delayed messages use their inbox index as request id
+// - arbos/tx_processor.go: StartTxHook() for `ArbitrumSubmitRetryableTx`
+//   - the ticket id is the submit-retryable tx hash
+//   - the auto-redeem is scheduled with nonce 0, the L2 base fee as gas fee cap and the
+//     refund pool left after the submission and gas fees are taken
+// - arbos/retryables/retryable.go: RetryableSubmissionFee(), Retryable.MakeTx()
+
+/// Gas below which ArbOS does not schedule an auto-redeem.
+const TX_GAS: u64 = 21_000;
+
+/// Returns the submission fee charged for a retryable with `data_len` bytes of calldata.
+pub fn retryable_submission_fee(data_len: usize, l1_base_fee: U256) -> U256 {
+    l1_base_fee * U256::from(1400 + 6 * data_len as u64)
+}
+
+/// Removes up to `take` from `pool`, returning the amount removed.
+fn take_funds(pool: &mut U256, take: U256) -> U256 {
+    let taken = take.min(*pool);
+    *pool -= taken;
+    taken
+}
+
+/// Delayed inbox message an L1 submission will be delivered as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InboxSubmission {
+    /// L2 chain id.
+    pub chain_id: ChainId,
+    /// Delayed inbox index of the message, from the L1 `MessageDelivered` log.
+    pub message_index: u64,
+    /// Message sender as recorded by the bridge, i.e. the aliased L1 sender.
+    pub sender: Address,
+    /// L1 base fee recorded with the message.
+    pub l1_base_fee: U256,
+}
+
+impl InboxSubmission {
+    /// Creates a submission for the delayed message at `message_index`.
+    pub const fn new(
+        chain_id: ChainId,
+        message_index: u64,
+        sender: Address,
+        l1_base_fee: U256,
+    ) -> Self {
+        Self {
+            chain_id,
+            message_index,
+            sender,
+            l1_base_fee,
+        }
+    }
+
+    /// Returns the request id of the message: its index as a 32-byte word.
+    pub fn request_id(&self) -> B256 {
+        U256::from(self.message_index).into()
+    }
+
+    /// Returns the `TxDeposit` an ETH deposit of `value` to `to` produces.
+    pub fn deposit(&self, to: Address, value: U256) -> ArbTxEnvelope {
+        TxDeposit {
+            chain_id: U256::from(self.chain_id),
+            request_id: self.request_id(),
+            from: self.sender,
+            to,
+            value,
+        }
+        .into()
+    }
+
+    /// Returns the transactions a `createRetryableTicket` submission produces.
+    pub fn retryable(&self, params: &RetryableSubmission) -> PredictedRetryable {
+        let submit = SubmitRetryableTx::new(
+            U256::from(self.chain_id),
+            self.request_id(),
+            self.sender,
+            self.l1_base_fee,
+            params.deposit_value,
+            params.max_fee_per_gas,
+            U256::from(params.gas_limit),
+            params.to,
+            params.l2_call_value,
+            params.call_value_refund_address,
+            params.max_submission_fee,
+            params.excess_fee_refund_address,
+            params.data.clone(),
+        );
+        PredictedRetryable {
+            ticket_id: submit.tx_hash(),
+            submission: *self,
+            params: params.clone(),
+            submit: submit.into(),
+        }
+    }
+}
+
+/// Parameters of an L1 `createRetryableTicket` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryableSubmission {
+    /// L2 call target, or `Create` for a contract deployment.
+    pub to: TxKind,
+    /// Value passed to the L2 call.
+    pub l2_call_value: U256,
+    /// ETH deposited with the ticket, i.e. the L1 `msg.value`.
+    pub deposit_value: U256,
+    /// Maximum submission fee the sender pays.
+    pub max_submission_fee: U256,
+    /// Address receiving unused fees.
+    pub excess_fee_refund_address: Address,
+    /// Beneficiary receiving the call value if the ticket expires or is cancelled.
+    pub call_value_refund_address: Address,
+    /// Gas limit of the auto-redeem.
+    pub gas_limit: u64,
+    /// Maximum fee per gas of the auto-redeem.
+    pub max_fee_per_gas: U256,
+    /// Calldata of the L2 call.
+    pub data: Bytes,
+}
+
+/// Transactions predicted for a retryable submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PredictedRetryable {
+    /// The `SubmitRetryableTx` that creates the ticket.
+    pub submit: ArbTxEnvelope,
+    /// Id of the ticket, equal to the hash of `submit`.
+    pub ticket_id: B256,
+    submission: InboxSubmission,
+    params: RetryableSubmission,
+}
+
+impl PredictedRetryable {
+    /// Returns the first `TxRetry` ArbOS schedules to auto-redeem the ticket, executed at
+    /// L2 base fee `l2_base_fee`.
+    ///
+    /// Returns `None` when ArbOS would not schedule an auto-redeem: the submission fee exceeds
+    /// the maximum, the gas limit is below 21000 or the fee cap is below the base fee. The
+    /// deposit is assumed to cover the maximum gas cost, as the L1 inbox enforces.
+    pub fn auto_redeem(&self, l2_base_fee: U256) -> Option<ArbTxEnvelope> {
+        let params = &self.params;
+        let submission_fee =
+            retryable_submission_fee(params.data.len(), self.submission.l1_base_fee);
+        if params.max_submission_fee < submission_fee
+            || params.gas_limit < TX_GAS
+            || params.max_fee_per_gas < l2_base_fee
+        {
+            return None;
+        }
+
+        let mut available_refund = params.deposit_value;
+        take_funds(&mut available_refund, params.l2_call_value);
+        let withheld_submission_fee = take_funds(&mut available_refund, submission_fee);
+        take_funds(
+            &mut available_refund,
+            params.max_submission_fee - submission_fee,
+        );
+        let gas = U256::from(params.gas_limit);
+        let withheld_gas_funds = take_funds(&mut available_refund, l2_base_fee * gas);
+        take_funds(
+            &mut available_refund,
+            (params.max_fee_per_gas - l2_base_fee) * gas,
+        );
+        available_refund += withheld_gas_funds + withheld_submission_fee;
+
+        Some(
+            TxRetry {
+                chain_id: U256::from(self.submission.chain_id),
+                nonce: 0,
+                from: self.submission.sender,
+                gas_fee_cap: l2_base_fee,
+                gas_limit: params.gas_limit,
+                to: params.to,
+                value: params.l2_call_value,
+                input: params.data.clone(),
+                ticket_id: self.ticket_id,
+                refund_to: params.excess_fee_refund_address,
+                max_refund: available_refund,
+                submission_fee_refund: submission_fee,
+            }
+            .into(),
+        )
+    }
+
+    /// Returns the hash of the auto-redeem, see [`Self::auto_redeem`].
+    pub fn auto_redeem_hash(&self, l2_base_fee: U256) -> Option<B256> {
+        self.auto_redeem(l2_base_fee).map(|tx| tx.tx_hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inbox::delayed::{DelayedMessageData, DeliveredMessage},
+        parse_l2_transactions,
+    };
+    use alloy_primitives::{address, keccak256};
+    use arb_sequencer_network::sequencer::feed::MessageType;
+
+    fn delivered(
+        submission: &InboxSubmission,
+        kind: MessageType,
+        data: &[u8],
+    ) -> Vec<ArbTxEnvelope> {
+        let delivered = DeliveredMessage {
+            message_index: submission.message_index,
+            before_inbox_acc: B256::ZERO,
+            inbox: Address::ZERO,
+            kind: kind.to_u8(),
+            sender: submission.sender,
+            message_data_hash: keccak256(data),
+            base_fee_l1: submission.l1_base_fee,
+            timestamp: 1,
+            block_number: 1,
+        };
+        let msg = delivered
+            .with_data(&DelayedMessageData {
+                message_index: submission.message_index,
+                data: Bytes::copy_from_slice(data),
+            })
+            .unwrap();
+        parse_l2_transactions(&msg, submission.chain_id, 32).unwrap()
+    }
+
+    #[test]
+    fn predicted_deposit_matches_parsed_message() {
+        let submission = InboxSubmission::new(
+            412346,
+            21,
+            address!("0x1111111111111111111111111111111111111111"),
+            U256::from(7),
+        );
+        let to = address!("0x2222222222222222222222222222222222222222");
+        let mut data = to.to_vec();
+        data.extend_from_slice(&U256::from(1_000).to_be_bytes::<32>());
+
+        let predicted = submission.deposit(to, U256::from(1_000));
+        let parsed = delivered(&submission, MessageType::EthDeposit, &data);
+        assert_eq!(parsed, core::slice::from_ref(&predicted));
+        assert_eq!(parsed[0].tx_hash(), predicted.tx_hash());
+    }
+
+    #[test]
+    fn predicted_retryable_matches_parsed_message_and_schedules_redeem() {
+        let submission = InboxSubmission::new(
+            412346,
+            5,
+            address!("0x3333333333333333333333333333333333333333"),
+            U256::from(10),
+        );
+        let refund = address!("0x4444444444444444444444444444444444444444");
+        let params = RetryableSubmission {
+            to: TxKind::Call(address!("0x5555555555555555555555555555555555555555")),
+            l2_call_value: U256::from(1_000),
+            deposit_value: U256::from(10_000_000),
+            max_submission_fee: U256::from(20_000),
+            excess_fee_refund_address: refund,
+            call_value_refund_address: refund,
+            gas_limit: 100_000,
+            max_fee_per_gas: U256::from(50),
+            data: Bytes::from_static(&[0xde, 0xad]),
+        };
+        let predicted = submission.retryable(&params);
+
+        let mut data = Vec::new();
+        for word in [
+            B256::left_padding_from(params.to.to().unwrap().as_slice()),
+            params.l2_call_value.into(),
+            params.deposit_value.into(),
+            params.max_submission_fee.into(),
+            B256::left_padding_from(refund.as_slice()),
+            B256::left_padding_from(refund.as_slice()),
+            U256::from(params.gas_limit).into(),
+            params.max_fee_per_gas.into(),
+            U256::from(params.data.len()).into(),
+        ] {
+            data.extend_from_slice(word.as_slice());
+        }
+        data.extend_from_slice(&params.data);
+        let parsed = delivered(&submission, MessageType::SubmitRetryable, &data);
+        assert_eq!(parsed, core::slice::from_ref(&predicted.submit));
+        assert_eq!(parsed[0].tx_hash(), predicted.ticket_id);
+
+        let ArbTxEnvelope::Retry(retry) = predicted.auto_redeem(U256::from(20)).unwrap() else {
+            panic!("auto-redeem must be a TxRetry");
+        };
+        let submission_fee = U256::from(10 * (1400 + 6 * 2));
+        assert_eq!(retry.ticket_id, predicted.ticket_id);
+        assert_eq!(retry.gas_fee_cap, U256::from(20));
+        assert_eq!(retry.submission_fee_refund, submission_fee);
+        // Deposit minus call value, the excess submission fee and the gas price refund.
+        assert_eq!(
+            retry.max_refund,
+            U256::from(10_000_000 - 1_000 - (20_000 - 14_120) - 30 * 100_000)
+        );
+        assert!(predicted.auto_redeem(U256::from(51)).is_none());
+    }
+}
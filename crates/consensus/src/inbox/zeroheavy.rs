@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+// Nitro reference
+// - zeroheavy/zeroheavy.go:
+//   - bit-level code that makes zero bytes cheap in calldata: `0` -> 0x00, `10` -> 0x01,
+//     `11` followed by the 8 value bits -> any other byte
+//   - bits are read most significant first; a trailing partial code ends the stream
+
+/// Decodes a zeroheavy bit stream, stopping after `limit` output bytes.
+///
+/// A trailing partial code (the encoder pads the last byte with one bits) ends the output.
+pub fn decode(input: &[u8], limit: usize) -> Vec<u8> {
+    let mut bits = input
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1 == 1));
+    let mut out = Vec::new();
+    while out.len() < limit {
+        let Some(first) = bits.next() else { break };
+        if !first {
+            out.push(0);
+            continue;
+        }
+        let Some(second) = bits.next() else { break };
+        if !second {
+            out.push(1);
+            continue;
+        }
+        let mut value = 0u8;
+        for _ in 0..8 {
+            let Some(bit) = bits.next() else {
+                return out;
+            };
+            value = (value << 1) | u8::from(bit);
+        }
+        out.push(value);
+    }
+    out
+}
+
+/// Encodes `input` as a zeroheavy bit stream.
+pub fn encode(input: &[u8]) -> Vec<u8> {
+    let mut bits = Vec::with_capacity(input.len() * 2);
+    for &byte in input {
+        match byte {
+            0 => bits.push(false),
+            1 => bits.extend([true, false]),
+            _ => {
+                bits.extend([true, true]);
+                bits.extend((0..8).rev().map(|shift| (byte >> shift) & 1 == 1));
+            }
+        }
+    }
+    bits.chunks(8)
+        .map(|chunk| {
+            (0..8).fold(0u8, |acc, i| {
+                (acc << 1) | u8::from(chunk.get(i).copied().unwrap_or(true))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zeroheavy_round_trips_and_shrinks_zeros() {
+        let data = [0, 0, 0, 1, 0xff, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let encoded = encode(&data);
+        assert!(encoded.len() < data.len());
+        assert_eq!(decode(&encoded, usize::MAX), data);
+        assert_eq!(decode(&encoded, 4), data[..4]);
+    }
+}
@@ -9,14 +9,29 @@
 
 extern crate alloc;
 
+/// L1-to-L2 address aliasing.
+pub mod alias;
+/// Block root and hash verification.
+pub mod block;
 /// Arbitrum header `extraData` decoding types.
 pub mod header;
+/// L1 inbox data decoding.
+pub mod inbox;
+/// L2-to-L1 send accumulator and outbox proofs.
+pub mod outbox;
 /// Arbitrum receipt body and envelope types.
 pub mod receipt;
 /// Arbitrum transaction body, envelope, and helpers.
 pub mod transactions;
 
-pub use header::{ArbHeaderDecodeError, ArbHeaderInfo};
+pub use alias::{L1_TO_L2_ALIAS_OFFSET, apply_l1_to_l2_alias, undo_l1_to_l2_alias};
+pub use block::{BlockVerificationError, calculate_transaction_root, verify_block};
+pub use header::{
+    ArbHeader, ArbHeaderDecodeError, ArbHeaderError, ArbHeaderInfo, ArbHeaderValidationError,
+};
+pub use outbox::{L2ToL1Send, OutboxProof, SendMerkleAccumulator};
 pub use receipt::{ArbReceipt, ArbReceiptEnvelope};
+pub use transactions::l2_message::{L2Message, L2MessageKind};
+pub use transactions::parse_l2::{ParseL2Error, parse_l2_transactions};
 pub use transactions::typed::ArbitrumTypedTransaction as ArbTypedTransaction;
 pub use transactions::{ArbTxEnvelope, ArbTxType};
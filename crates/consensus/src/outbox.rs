@@ -0,0 +1,366 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+
+use crate::ArbHeaderInfo;
+
+// Nitro reference
+// - precompiles/ArbSys.go: SendTxToL1() hashes the send and appends it to the accumulator;
+//   SendMerkleTreeState() returns (size, root, partials)
+// - arbos/merkleAccumulator/merkleAccumulator.go:
+//   - leaves are keccak(sendHash)
+//   - Append() merges equal-sized subtrees into partials, CalcNumPartials() = log2ceil(size)
+//   - Root() folds the partials, padding the running hash with zero words up to each level
+// - contracts/src/bridge/Outbox.sol: executeTransaction() rejects proofs of 256 or more
+//   nodes and indices that do not fit in the proof, then checks
+//   MerkleLib.calculateRoot(proof, index, keccak(item)) against the confirmed send root
+
+/// Maximum number of nodes the L1 outbox accepts in a proof.
+pub const MAX_OUTBOX_PROOF_LEN: usize = 255;
+
+/// Error while rebuilding the send accumulator or checking an outbox proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutboxError {
+    /// The number of partials does not match the accumulator size.
+    PartialsLength {
+        /// Number of partials expected for the size.
+        expected: usize,
+        /// Number of partials supplied.
+        got: usize,
+    },
+    /// The proof has more nodes than the outbox accepts.
+    ProofTooLong {
+        /// Number of nodes in the proof.
+        len: usize,
+    },
+    /// The leaf index does not fit in the proof path.
+    PathNotMinimal {
+        /// Leaf index of the send.
+        index: u64,
+        /// Number of nodes in the proof.
+        len: usize,
+    },
+    /// The leaf index is not below the send count of the header.
+    UnknownSend {
+        /// Leaf index of the send.
+        index: u64,
+        /// Send count of the header.
+        send_count: u64,
+    },
+    /// The proof does not hash to the expected root.
+    RootMismatch {
+        /// Root the proof was checked against.
+        expected: B256,
+        /// Root computed from the proof.
+        computed: B256,
+    },
+}
+
+impl fmt::Display for OutboxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PartialsLength { expected, got } => {
+                write!(f, "expected {expected} send merkle partials, got {got}")
+            }
+            Self::ProofTooLong { len } => write!(
+                f,
+                "outbox proof has {len} nodes, at most {MAX_OUTBOX_PROOF_LEN} allowed"
+            ),
+            Self::PathNotMinimal { index, len } => {
+                write!(
+                    f,
+                    "send index {index} does not fit in a proof of {len} nodes"
+                )
+            }
+            Self::UnknownSend { index, send_count } => {
+                write!(f, "send index {index} is not below send count {send_count}")
+            }
+            Self::RootMismatch { expected, computed } => {
+                write!(f, "outbox proof root {computed} does not match {expected}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for OutboxError {}
+
+/// L2-to-L1 send, as emitted by `ArbSys` in the `L2ToL1Tx` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L2ToL1Send {
+    /// L2 sender of the message.
+    pub caller: Address,
+    /// L1 destination of the message.
+    pub destination: Address,
+    /// L2 block number the send was made in.
+    pub arb_block_num: u64,
+    /// L1 block number observed by ArbOS when the send was made.
+    pub eth_block_num: u64,
+    /// L2 timestamp of the send.
+    pub timestamp: u64,
+    /// ETH value sent to L1.
+    pub callvalue: U256,
+    /// Calldata for the L1 call.
+    pub data: Bytes,
+}
+
+impl L2ToL1Send {
+    /// Returns the send hash, the `hash` field of `L2ToL1Tx` and the outbox item hash.
+    pub fn hash(&self) -> B256 {
+        let mut preimage = Vec::with_capacity(20 + 20 + 32 * 4 + self.data.len());
+        preimage.extend_from_slice(self.caller.as_slice());
+        preimage.extend_from_slice(self.destination.as_slice());
+        preimage.extend_from_slice(&U256::from(self.arb_block_num).to_be_bytes::<32>());
+        preimage.extend_from_slice(&U256::from(self.eth_block_num).to_be_bytes::<32>());
+        preimage.extend_from_slice(&U256::from(self.timestamp).to_be_bytes::<32>());
+        preimage.extend_from_slice(&self.callvalue.to_be_bytes::<32>());
+        preimage.extend_from_slice(&self.data);
+        keccak256(preimage)
+    }
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    keccak256([left, right].concat())
+}
+
+/// Returns the number of partials an accumulator of `size` leaves stores.
+pub const fn send_merkle_num_partials(size: u64) -> usize {
+    (u64::BITS - size.leading_zeros()) as usize
+}
+
+/// ArbOS send Merkle accumulator, committed to by the header send root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SendMerkleAccumulator {
+    size: u64,
+    partials: Vec<B256>,
+}
+
+impl SendMerkleAccumulator {
+    /// Creates an empty accumulator.
+    pub const fn new() -> Self {
+        Self {
+            size: 0,
+            partials: Vec::new(),
+        }
+    }
+
+    /// Rebuilds an accumulator from `ArbSys.sendMerkleTreeState` output.
+    pub fn from_state(size: u64, partials: Vec<B256>) -> Result<Self, OutboxError> {
+        let expected = send_merkle_num_partials(size);
+        if partials.len() != expected {
+            return Err(OutboxError::PartialsLength {
+                expected,
+                got: partials.len(),
+            });
+        }
+        Ok(Self { size, partials })
+    }
+
+    /// Returns the number of sends appended.
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the partials, lowest level first. Empty levels are zero.
+    pub fn partials(&self) -> &[B256] {
+        &self.partials
+    }
+
+    /// Appends a send by its hash.
+    pub fn append(&mut self, send_hash: B256) {
+        let num_partials = send_merkle_num_partials(self.size);
+        self.size += 1;
+        let mut so_far = keccak256(send_hash);
+        for level in 0..num_partials {
+            let partial = &mut self.partials[level];
+            if partial.is_zero() {
+                *partial = so_far;
+                return;
+            }
+            so_far = hash_pair(*partial, so_far);
+            *partial = B256::ZERO;
+        }
+        self.partials.push(so_far);
+    }
+
+    /// Returns the send root, zero for an empty accumulator.
+    pub fn root(&self) -> B256 {
+        let mut hash_so_far: Option<(B256, u64)> = None;
+        for (level, partial) in self.partials.iter().enumerate() {
+            if partial.is_zero() {
+                continue;
+            }
+            let capacity = 1u64 << level;
+            hash_so_far = Some(match hash_so_far {
+                None => (*partial, capacity),
+                Some((mut hash, mut capacity_in_hash)) => {
+                    while capacity_in_hash < capacity {
+                        hash = hash_pair(hash, B256::ZERO);
+                        capacity_in_hash *= 2;
+                    }
+                    (hash_pair(*partial, hash), capacity * 2)
+                }
+            });
+        }
+        hash_so_far.map_or(B256::ZERO, |(hash, _)| hash)
+    }
+}
+
+/// Proof that a send is included in a send root, as returned by
+/// `NodeInterface.constructOutboxProof`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxProof {
+    /// Hash of the proven send.
+    pub send: B256,
+    /// Send root the proof was built for.
+    pub root: B256,
+    /// Sibling nodes from the leaf up.
+    pub proof: Vec<B256>,
+    /// Leaf index of the send, its `position` in `L2ToL1Tx`.
+    pub index: u64,
+}
+
+impl OutboxProof {
+    /// Returns the root the proof hashes to, applying the outbox's length checks.
+    pub fn calculate_root(&self) -> Result<B256, OutboxError> {
+        let len = self.proof.len();
+        if len > MAX_OUTBOX_PROOF_LEN {
+            return Err(OutboxError::ProofTooLong { len });
+        }
+        if len < 64 && self.index >> len != 0 {
+            return Err(OutboxError::PathNotMinimal {
+                index: self.index,
+                len,
+            });
+        }
+        Ok(self
+            .proof
+            .iter()
+            .enumerate()
+            .fold(keccak256(self.send), |hash, (level, node)| {
+                if level < 64 && self.index & (1 << level) != 0 {
+                    hash_pair(*node, hash)
+                } else {
+                    hash_pair(hash, *node)
+                }
+            }))
+    }
+
+    /// Checks the proof hashes to both its own root and `send_root`.
+    pub fn verify(&self, send_root: B256) -> Result<(), OutboxError> {
+        let computed = self.calculate_root()?;
+        for expected in [self.root, send_root] {
+            if computed != expected {
+                return Err(OutboxError::RootMismatch { expected, computed });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the proof against the send root of a header, and that the header includes the
+    /// send.
+    pub fn verify_header(&self, info: &ArbHeaderInfo) -> Result<(), OutboxError> {
+        if self.index >= info.send_count {
+            return Err(OutboxError::UnknownSend {
+                index: self.index,
+                send_count: info.send_count,
+            });
+        }
+        self.verify(info.send_root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sends(count: u64) -> Vec<B256> {
+        (0..count)
+            .map(|i| {
+                L2ToL1Send {
+                    caller: Address::repeat_byte(1),
+                    destination: Address::repeat_byte(2),
+                    arb_block_num: 100 + i,
+                    eth_block_num: 10,
+                    timestamp: 1_700_000_000,
+                    callvalue: U256::from(i),
+                    data: Bytes::new(),
+                }
+                .hash()
+            })
+            .collect()
+    }
+
+    /// Builds the tree level by level, padding odd levels with a zero word.
+    fn levels(sends: &[B256]) -> Vec<Vec<B256>> {
+        let mut levels = vec![sends.iter().map(keccak256).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let mut level = levels.last().unwrap().clone();
+            if level.len() % 2 == 1 {
+                level.push(B256::ZERO);
+            }
+            let next = level.chunks(2).map(|p| hash_pair(p[0], p[1])).collect();
+            *levels.last_mut().unwrap() = level;
+            levels.push(next);
+        }
+        levels
+    }
+
+    fn proof(sends: &[B256], index: u64) -> OutboxProof {
+        let levels = levels(sends);
+        OutboxProof {
+            send: sends[index as usize],
+            root: levels.last().unwrap()[0],
+            proof: levels[..levels.len() - 1]
+                .iter()
+                .enumerate()
+                .map(|(level, nodes)| nodes[((index >> level) ^ 1) as usize])
+                .collect(),
+            index,
+        }
+    }
+
+    #[test]
+    fn accumulator_matches_padded_tree_and_verifies_proofs() {
+        let all = sends(11);
+        let mut acc = SendMerkleAccumulator::new();
+        assert_eq!(acc.root(), B256::ZERO);
+        for size in 1..=all.len() {
+            acc.append(all[size - 1]);
+            let sends = &all[..size];
+            assert_eq!(acc.root(), levels(sends).last().unwrap()[0], "size {size}");
+            assert_eq!(
+                SendMerkleAccumulator::from_state(acc.size(), acc.partials().to_vec()).unwrap(),
+                acc
+            );
+            for index in 0..size as u64 {
+                let info = ArbHeaderInfo {
+                    send_root: acc.root(),
+                    send_count: size as u64,
+                    ..Default::default()
+                };
+                proof(sends, index).verify_header(&info).unwrap();
+            }
+        }
+
+        let mut bad = proof(&all, 3);
+        bad.send = all[4];
+        assert!(matches!(
+            bad.verify(acc.root()),
+            Err(OutboxError::RootMismatch { .. })
+        ));
+        bad = proof(&all, 3);
+        bad.index = 1 << bad.proof.len();
+        assert!(matches!(
+            bad.calculate_root(),
+            Err(OutboxError::PathNotMinimal { .. })
+        ));
+        assert_eq!(
+            SendMerkleAccumulator::from_state(4, vec![B256::ZERO]),
+            Err(OutboxError::PartialsLength {
+                expected: 3,
+                got: 1
+            })
+        );
+    }
+}
@@ -117,6 +117,12 @@ pub enum ArbReceiptEnvelope<T = Log> {
     /// Arbitrum internal system receipt (`type = 0x6a`).
     #[serde(rename = "0x6a", alias = "0x6A")]
     Internal(ReceiptWithBloom<ArbReceipt<T>>),
+    /// Classic (pre-Nitro) Arbitrum receipt (`type = 0x78`).
+    ///
+    /// Like Nitro, the 2718 encoding omits the type byte so classic receipt roots still
+    /// match; untyped bytes therefore decode as [`Self::Legacy`].
+    #[serde(rename = "0x78")]
+    ArbitrumLegacy(ReceiptWithBloom<ArbReceipt<T>>),
 }
 
 impl<T> ArbReceiptEnvelope<T> {
@@ -132,9 +138,15 @@ impl<T> ArbReceiptEnvelope<T> {
             | Self::Contract(r)
             | Self::Retry(r)
             | Self::SubmitRetryable(r)
-            | Self::Internal(r) => r,
+            | Self::Internal(r)
+            | Self::ArbitrumLegacy(r) => r,
         }
     }
+
+    /// Returns `true` for receipts encoded without a type byte.
+    const fn is_untyped(&self) -> bool {
+        matches!(self, Self::Legacy(_) | Self::ArbitrumLegacy(_))
+    }
 }
 
 impl<T> TxReceipt for ArbReceiptEnvelope<T>
@@ -189,7 +201,8 @@ where
             | Self::Contract(r)
             | Self::Retry(r)
             | Self::SubmitRetryable(r)
-            | Self::Internal(r) => r.receipt.inner.logs,
+            | Self::Internal(r)
+            | Self::ArbitrumLegacy(r) => r.receipt.inner.logs,
         }
     }
 }
@@ -230,6 +243,7 @@ impl<T> Typed2718 for ArbReceiptEnvelope<T> {
             Self::Retry(_) => 0x68,
             Self::SubmitRetryable(_) => 0x69,
             Self::Internal(_) => 0x6a,
+            Self::ArbitrumLegacy(_) => 0x78,
         }
     }
 }
@@ -238,8 +252,12 @@ impl<T> Encodable2718 for ArbReceiptEnvelope<T>
 where
     T: Encodable + Send + Sync,
 {
+    fn type_flag(&self) -> Option<u8> {
+        (!self.is_untyped()).then(|| self.ty())
+    }
+
     fn encode_2718_len(&self) -> usize {
-        self.as_receipt_with_bloom().length() + !self.is_legacy() as usize
+        self.as_receipt_with_bloom().length() + !self.is_untyped() as usize
     }
 
     fn encode_2718(&self, out: &mut dyn BufMut) {
@@ -248,6 +266,30 @@ where
         }
         self.as_receipt_with_bloom().encode(out);
     }
+
+    fn network_len(&self) -> usize {
+        let payload_length = self.encode_2718_len();
+        if self.is_untyped() {
+            payload_length
+        } else {
+            alloy_rlp::Header {
+                list: false,
+                payload_length,
+            }
+            .length_with_payload()
+        }
+    }
+
+    fn network_encode(&self, out: &mut dyn BufMut) {
+        if !self.is_untyped() {
+            alloy_rlp::Header {
+                list: false,
+                payload_length: self.encode_2718_len(),
+            }
+            .encode(out);
+        }
+        self.encode_2718(out);
+    }
 }
 
 impl<T> Decodable2718 for ArbReceiptEnvelope<T>
@@ -268,6 +310,7 @@ where
             0x68 => Ok(Self::Retry(receipt)),
             0x69 => Ok(Self::SubmitRetryable(receipt)),
             0x6a => Ok(Self::Internal(receipt)),
+            0x78 => Ok(Self::ArbitrumLegacy(receipt)),
             _ => Err(Eip2718Error::UnexpectedType(ty)),
         }
     }
@@ -276,3 +319,39 @@ where
         Ok(Self::Legacy(Decodable::decode(buf)?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::{Address, Bytes, LogData};
+
+    #[test]
+    fn classic_receipt_encodes_untyped() {
+        let receipt = ReceiptWithBloom {
+            receipt: ArbReceipt::new(Receipt {
+                status: Eip658Value::Eip658(true),
+                cumulative_gas_used: 21_000,
+                logs: vec![Log {
+                    address: Address::repeat_byte(0x11),
+                    data: LogData::new_unchecked(Vec::new(), Bytes::from_static(&[1])),
+                }],
+            }),
+            logs_bloom: Bloom::ZERO,
+        };
+        let classic = ArbReceiptEnvelope::ArbitrumLegacy(receipt.clone());
+        let legacy = ArbReceiptEnvelope::Legacy(receipt.clone());
+
+        assert_eq!(classic.ty(), 0x78);
+        assert_eq!(classic.encoded_2718(), legacy.encoded_2718());
+        assert_eq!(classic.network_len(), legacy.network_len());
+        assert_eq!(alloy_rlp::encode(&classic), alloy_rlp::encode(&legacy));
+
+        let mut typed = vec![0x78];
+        receipt.encode(&mut typed);
+        assert_eq!(
+            ArbReceiptEnvelope::<Log>::decode_2718(&mut typed.as_slice()).unwrap(),
+            classic
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use alloy_consensus::{Transaction, TxLegacy, Typed2718, transaction::from_eip155_value};
+use alloy_eips::{
+    Decodable2718, Encodable2718,
+    eip2718::{Eip2718Error, Eip2718Result},
+    eip2930::AccessList,
+    eip7702::SignedAuthorization,
+};
+use alloy_primitives::{Address, B256, Bytes, ChainId, Signature, TxHash, TxKind, U256};
+use alloy_rlp::{Decodable, Encodable, Header};
+use bytes::BufMut;
+use serde::{Deserialize, Serialize};
+
+use crate::transactions::ArbTxType;
+
+// Nitro reference
+// - go-ethereum (arbitrum fork) core/types/arb_types.go: ArbitrumLegacyTxData
+//   - RLP: [LegacyTx, HashOverride, EffectiveGasPrice, L1BlockNumber, OverrideSender?], with the
+//     embedded LegacyTx encoded as its own list
+//   - Hash() returns HashOverride, since classic hashes cannot be recomputed from the fields
+//   - EncodeOnlyLegacyInto() writes just the embedded LegacyTx, as stored in the transactions
+//     trie
+//   - OverrideSender is set for classic L1-originated transactions that carry no signature
+// - go-ethereum (arbitrum fork) internal/ethapi/api.go: newRPCTransaction() reports
+//   effectiveGasPrice and l1BlockNumber with omitempty
+
+/// Classic (pre-Nitro) Arbitrum transaction migrated into Nitro history (`type = 0x78`).
+#[derive(PartialEq, Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxArbitrumLegacy {
+    /// Sender nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Gas price offered by the sender.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_price: u128,
+    /// Gas limit for execution.
+    #[serde(rename = "gas", with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// Call target (or create).
+    #[serde(default, skip_serializing_if = "TxKind::is_create")]
+    pub to: TxKind,
+    /// ETH value transferred to the target.
+    pub value: U256,
+    /// Transaction calldata.
+    pub input: Bytes,
+    /// Raw signature `v`, EIP-155 encoded when the transaction is replay protected.
+    pub v: U256,
+    /// Signature `r`.
+    pub r: U256,
+    /// Signature `s`.
+    pub s: U256,
+    /// Classic transaction hash, zero when the JSON omits it.
+    #[serde(default, rename = "hash")]
+    pub hash_override: B256,
+    /// Gas price the transaction actually paid, zero when the JSON omits it.
+    #[serde(default, with = "alloy_serde::quantity")]
+    pub effective_gas_price: u64,
+    /// L1 block number the transaction was sequenced at, zero when the JSON omits it.
+    #[serde(default, with = "alloy_serde::quantity")]
+    pub l1_block_number: u64,
+    /// Sender of unsigned classic transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_sender: Option<Address>,
+}
+
+impl TxArbitrumLegacy {
+    /// Returns the classic transaction hash.
+    pub const fn tx_hash(&self) -> TxHash {
+        self.hash_override
+    }
+
+    /// Returns the EIP-155 chain id encoded in `v`, if any.
+    pub fn signature_chain_id(&self) -> Option<ChainId> {
+        u128::try_from(self.v)
+            .ok()
+            .and_then(from_eip155_value)
+            .and_then(|(_, chain_id)| chain_id)
+    }
+
+    /// Returns the embedded legacy transaction, with the chain id taken from `v`.
+    pub fn legacy_tx(&self) -> TxLegacy {
+        TxLegacy {
+            chain_id: self.signature_chain_id(),
+            nonce: self.nonce,
+            gas_price: self.gas_price,
+            gas_limit: self.gas_limit,
+            to: self.to,
+            value: self.value,
+            input: self.input.clone(),
+        }
+    }
+
+    /// Returns the embedded legacy transaction and its signature.
+    ///
+    /// Returns `None` when `v` is not a valid legacy or EIP-155 value, as for unsigned
+    /// classic transactions.
+    pub fn as_signed_legacy(&self) -> Option<(TxLegacy, Signature)> {
+        let (parity, _) = from_eip155_value(u128::try_from(self.v).ok()?)?;
+        Some((self.legacy_tx(), Signature::new(self.r, self.s, parity)))
+    }
+
+    /// Returns the sender: the override for unsigned classic transactions, otherwise the
+    /// recovered signer.
+    #[cfg_attr(not(feature = "k256"), allow(clippy::missing_const_for_fn))]
+    pub fn sender(&self) -> Result<Address, alloy_primitives::SignatureError> {
+        if let Some(sender) = self.override_sender {
+            return Ok(sender);
+        }
+        #[cfg(feature = "k256")]
+        {
+            use alloy_consensus::SignableTransaction;
+
+            let (tx, signature) =
+                self.as_signed_legacy()
+                    .ok_or(alloy_primitives::SignatureError::FromBytes(
+                        "invalid legacy signature value",
+                    ))?;
+            signature.recover_address_from_prehash(&tx.signature_hash())
+        }
+        #[cfg(not(feature = "k256"))]
+        {
+            Err(alloy_primitives::SignatureError::FromBytes(
+                "signer recovery requires the `k256` feature",
+            ))
+        }
+    }
+
+    fn legacy_fields_length(&self) -> usize {
+        self.nonce.length()
+            + self.gas_price.length()
+            + self.gas_limit.length()
+            + self.to.length()
+            + self.value.length()
+            + self.input.length()
+            + self.v.length()
+            + self.r.length()
+            + self.s.length()
+    }
+
+    fn legacy_header(&self) -> Header {
+        Header {
+            list: true,
+            payload_length: self.legacy_fields_length(),
+        }
+    }
+
+    /// Encodes only the embedded legacy transaction, the form Nitro stores in the
+    /// transactions trie.
+    pub fn encode_only_legacy(&self, out: &mut dyn BufMut) {
+        self.legacy_header().encode(out);
+        self.nonce.encode(out);
+        self.gas_price.encode(out);
+        self.gas_limit.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.input.encode(out);
+        self.v.encode(out);
+        self.r.encode(out);
+        self.s.encode(out);
+    }
+
+    /// Encodes the inner RLP fields (without list header or type byte).
+    pub fn rlp_encode_fields(&self, out: &mut dyn BufMut) {
+        self.encode_only_legacy(out);
+        self.hash_override.encode(out);
+        self.effective_gas_price.encode(out);
+        self.l1_block_number.encode(out);
+        if let Some(sender) = &self.override_sender {
+            sender.encode(out);
+        }
+    }
+
+    /// Returns the encoded RLP payload length for the inner fields.
+    pub fn rlp_encoded_fields_length(&self) -> usize {
+        self.legacy_header().length_with_payload()
+            + self.hash_override.length()
+            + self.effective_gas_price.length()
+            + self.l1_block_number.length()
+            + self.override_sender.as_ref().map_or(0, Encodable::length)
+    }
+
+    /// Returns the RLP list header for the inner payload.
+    pub fn rlp_header(&self) -> Header {
+        Header {
+            list: true,
+            payload_length: self.rlp_encoded_fields_length(),
+        }
+    }
+
+    /// Encodes the transaction in RLP list form (without type byte).
+    pub fn rlp_encode(&self, out: &mut dyn BufMut) {
+        self.rlp_header().encode(out);
+        self.rlp_encode_fields(out);
+    }
+
+    fn rlp_encoded_length(&self) -> usize {
+        self.rlp_header().length_with_payload()
+    }
+
+    /// Decodes the transaction from its RLP list form (without type byte).
+    pub fn rlp_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if header.payload_length > buf.len() {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let (mut body, rest) = buf.split_at(header.payload_length);
+
+        let legacy = Header::decode(&mut body)?;
+        if !legacy.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let before = body.len();
+        let nonce = Decodable::decode(&mut body)?;
+        let gas_price = Decodable::decode(&mut body)?;
+        let gas_limit = Decodable::decode(&mut body)?;
+        let to = Decodable::decode(&mut body)?;
+        let value = Decodable::decode(&mut body)?;
+        let input = Decodable::decode(&mut body)?;
+        let v = Decodable::decode(&mut body)?;
+        let r = Decodable::decode(&mut body)?;
+        let s = Decodable::decode(&mut body)?;
+        if before - body.len() != legacy.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: legacy.payload_length,
+                got: before - body.len(),
+            });
+        }
+
+        let hash_override = Decodable::decode(&mut body)?;
+        let effective_gas_price = Decodable::decode(&mut body)?;
+        let l1_block_number = Decodable::decode(&mut body)?;
+        let override_sender = if body.is_empty() {
+            None
+        } else {
+            Some(Decodable::decode(&mut body)?)
+        };
+        if !body.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        *buf = rest;
+
+        Ok(Self {
+            nonce,
+            gas_price,
+            gas_limit,
+            to,
+            value,
+            input,
+            v,
+            r,
+            s,
+            hash_override,
+            effective_gas_price,
+            l1_block_number,
+            override_sender,
+        })
+    }
+}
+
+impl Typed2718 for TxArbitrumLegacy {
+    fn ty(&self) -> u8 {
+        ArbTxType::ArbitrumLegacy as u8
+    }
+}
+
+impl Decodable for TxArbitrumLegacy {
+    fn decode(data: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::rlp_decode(data)
+    }
+}
+
+impl Decodable2718 for TxArbitrumLegacy {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+        if ty != ArbTxType::ArbitrumLegacy as u8 {
+            return Err(Eip2718Error::UnexpectedType(ty));
+        }
+        Ok(Self::rlp_decode(buf)?)
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
+        Ok(Self::decode(buf)?)
+    }
+}
+
+impl Encodable2718 for TxArbitrumLegacy {
+    fn encode_2718_len(&self) -> usize {
+        self.rlp_encoded_length() + 1
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        out.put_u8(self.ty());
+        self.rlp_encode(out);
+    }
+
+    fn trie_hash(&self) -> B256 {
+        self.hash_override
+    }
+}
+
+impl Transaction for TxArbitrumLegacy {
+    fn chain_id(&self) -> Option<ChainId> {
+        self.signature_chain_id()
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        Some(self.gas_price)
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.gas_price
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        None
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        None
+    }
+
+    fn priority_fee_or_price(&self) -> u128 {
+        self.gas_price
+    }
+
+    fn effective_gas_price(&self, _base_fee: Option<u64>) -> u128 {
+        self.effective_gas_price as u128
+    }
+
+    fn is_dynamic_fee(&self) -> bool {
+        false
+    }
+
+    fn kind(&self) -> TxKind {
+        self.to
+    }
+
+    fn is_create(&self) -> bool {
+        self.to.is_create()
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn input(&self) -> &Bytes {
+        &self.input
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        None
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        None
+    }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    fn classic() -> TxArbitrumLegacy {
+        TxArbitrumLegacy {
+            nonce: 3,
+            gas_price: 1_000_000_000,
+            gas_limit: 600_000,
+            to: TxKind::Call(address!("0x1111111111111111111111111111111111111111")),
+            value: U256::from(5),
+            input: Bytes::from_static(&[0xaa, 0xbb]),
+            v: U256::from(42161 * 2 + 36),
+            r: U256::from(1),
+            s: U256::from(2),
+            hash_override: b256!(
+                "0x0d2d5f3d2b0bfd8a5d0de1c1a8c3fa9a9e2ae4cbd4b7c5b84d2ecf5d9a1b2c3d"
+            ),
+            effective_gas_price: 700_000_000,
+            l1_block_number: 12_525_700,
+            override_sender: None,
+        }
+    }
+
+    #[test]
+    fn classic_tx_round_trips_and_keeps_its_hash() {
+        for override_sender in [None, Some(Address::repeat_byte(0x22))] {
+            let tx = TxArbitrumLegacy {
+                override_sender,
+                ..classic()
+            };
+            let mut encoded = Vec::new();
+            tx.encode_2718(&mut encoded);
+            assert_eq!(encoded.len(), tx.encode_2718_len());
+            assert_eq!(encoded[0], 0x78);
+            // The embedded legacy transaction is a nested list.
+            let mut body = &encoded[1..];
+            Header::decode(&mut body).unwrap();
+            assert!(Header::decode(&mut body).unwrap().list);
+
+            let decoded = TxArbitrumLegacy::decode_2718(&mut encoded.as_slice()).unwrap();
+            assert_eq!(decoded, tx);
+            assert_eq!(decoded.tx_hash(), tx.hash_override);
+        }
+
+        let tx = classic();
+        assert_eq!(tx.chain_id(), Some(42161));
+        assert_eq!(tx.effective_gas_price(Some(1)), 700_000_000);
+        let (legacy, signature) = tx.as_signed_legacy().unwrap();
+        assert_eq!(legacy.chain_id, Some(42161));
+        assert!(signature.v());
+        assert_eq!(
+            TxArbitrumLegacy {
+                override_sender: Some(Address::repeat_byte(0x33)),
+                v: U256::ZERO,
+                ..tx
+            }
+            .sender()
+            .unwrap(),
+            Address::repeat_byte(0x33)
+        );
+    }
+}
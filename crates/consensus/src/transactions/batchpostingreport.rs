@@ -1,7 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::transactions::internal::ArbInternalTx;
-use crate::transactions::util::decode;
+use crate::transactions::util::{SequencerDecodeError, decode, decode_u64};
 use alloy_core::sol;
 use alloy_core::sol_types::SolCall;
 use alloy_primitives::{Address, Bytes, ChainId, FixedBytes, U256};
@@ -52,7 +52,7 @@ fn construct_batchreportv2_data(
     .into()
 }
 
-/// Decoded fields from an ArbOS batch posting report payload.
+/// Decoded arguments of an ArbOS `batchPostingReport` internal call.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BatchPostingReportFields {
     /// Batch timestamp recorded in the report.
@@ -61,20 +61,39 @@ pub struct BatchPostingReportFields {
     pub batch_poster: Address,
     /// Batch sequence number.
     pub batch_num: u64,
+    /// Gas charged for the batch data, including any extra gas.
+    pub batch_data_gas: u64,
     /// L1 base fee used for cost accounting.
     pub l1_base_fee: U256,
-    /// Additional gas charged by ArbOS for this batch.
-    pub extra_gas: u64,
-    /// Hash of the reported batch data.
-    pub data_hash: FixedBytes<32>,
 }
 
-const fn get_legacy_costs_from_batch_stats(stats: &BatchDataStats) -> u64 {
-    let mut gas = 4 * (stats.length - stats.non_zeros) + 16 * stats.non_zeros;
+const fn get_legacy_costs_from_batch_stats(
+    stats: &BatchDataStats,
+) -> Result<u64, SequencerDecodeError> {
+    let Some(zeros) = stats.length.checked_sub(stats.non_zeros) else {
+        return Err(SequencerDecodeError::InvalidBatchDataStats {
+            length: stats.length,
+            non_zeros: stats.non_zeros,
+        });
+    };
+    let overflow = SequencerDecodeError::Overflow {
+        field: "batch_data_stats",
+    };
+    let Some(data_gas) = zeros.checked_mul(4) else {
+        return Err(overflow);
+    };
+    let Some(non_zero_gas) = stats.non_zeros.checked_mul(16) else {
+        return Err(overflow);
+    };
     let keccak_words = words_for_bytes(stats.length);
-    gas += 30 + (keccak_words * 6);
-    gas += 2 * 20000;
-    gas
+    // data gas + keccak (30 + 6 per word) + two SSTOREs (20000 each)
+    match data_gas.checked_add(non_zero_gas) {
+        Some(gas) => match gas.checked_add(30 + keccak_words * 6 + 2 * 20000) {
+            Some(gas) => Ok(gas),
+            None => Err(overflow),
+        },
+        None => Err(overflow),
+    }
 }
 
 const fn words_for_bytes(nbytes: u64) -> u64 {
@@ -89,26 +108,26 @@ pub fn decode_fields_sequencer(
     arbos_version: u64,
     batch_data_stats: Option<BatchDataStats>,
     legacy_batch_gas: Option<u64>,
-) -> alloy_rlp::Result<ArbInternalTx> {
+) -> Result<ArbInternalTx, SequencerDecodeError> {
     let batch_timestamp: U256 = decode(buf)?;
     let batch_poster: Address = decode(buf)?;
     let _data_hash: FixedBytes<32> = decode(buf)?;
-    let batch_num: U256 = decode(buf)?;
+    let batch_num = decode_u64(buf, "batch_num")?;
     let l1_base_fee: U256 = decode(buf)?;
     let extra_gas = if buf.is_empty() { 0 } else { decode(buf)? };
 
-    let legacy_gas = if let Some(ref stats) = batch_data_stats {
-        let legacy_gas = get_legacy_costs_from_batch_stats(stats);
-        if legacy_batch_gas.is_some() && legacy_batch_gas.unwrap() != legacy_gas {
-            return Err(alloy_rlp::Error::Custom(
-                "Legacy gas doesn't fit local compute.",
-            ));
+    let legacy_gas = match (&batch_data_stats, legacy_batch_gas) {
+        (Some(stats), reported) => {
+            let computed = get_legacy_costs_from_batch_stats(stats)?;
+            if let Some(reported) = reported
+                && reported != computed
+            {
+                return Err(SequencerDecodeError::LegacyGasMismatch { reported, computed });
+            }
+            computed
         }
-        legacy_gas
-    } else {
-        legacy_batch_gas.ok_or(alloy_rlp::Error::Custom(
-            "Legacy gas missing for batch posting report",
-        ))?
+        (None, Some(reported)) => reported,
+        (None, None) => return Err(SequencerDecodeError::MissingBatchGasCost),
     };
 
     let data = if arbos_version < 50_u64 {
@@ -116,22 +135,16 @@ pub fn decode_fields_sequencer(
         construct_batchpostreport_data(
             batch_timestamp,
             batch_poster,
-            batch_num
-                .try_into()
-                .map_err(|_| alloy_rlp::Error::Overflow)?,
+            batch_num,
             batchgas,
             l1_base_fee,
         )
     } else {
-        let stats = batch_data_stats.ok_or(alloy_rlp::Error::Custom(
-            "Batch data stats required for arbos version >= 50",
-        ))?;
+        let stats = batch_data_stats.ok_or(SequencerDecodeError::MissingBatchDataStats)?;
         construct_batchreportv2_data(
             batch_timestamp,
             batch_poster,
-            batch_num
-                .try_into()
-                .map_err(|_| alloy_rlp::Error::Overflow)?,
+            batch_num,
             stats.length,
             stats.non_zeros,
             extra_gas,
@@ -141,3 +154,67 @@ pub fn decode_fields_sequencer(
 
     Ok(ArbInternalTx::new(chain_id, data))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions::internal::ArbosAction;
+
+    fn report_payload() -> Vec<u8> {
+        let mut payload = U256::from(1_700_000_000u64).to_be_bytes::<32>().to_vec();
+        payload.extend_from_slice(&[0x11; 20]);
+        payload.extend_from_slice(&[0x22; 32]);
+        payload.extend_from_slice(&U256::from(42).to_be_bytes::<32>());
+        payload.extend_from_slice(&U256::from(7).to_be_bytes::<32>());
+        payload.extend_from_slice(&5u64.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn decode_report_builds_v2_internal_tx() {
+        let stats = BatchDataStats {
+            length: 100,
+            non_zeros: 60,
+        };
+        let tx = decode_fields_sequencer(&mut &report_payload()[..], 42161, 50, Some(stats), None)
+            .unwrap();
+        assert_eq!(
+            tx.decode_action().unwrap(),
+            ArbosAction::BatchPostingReportV2 {
+                batch_timestamp: U256::from(1_700_000_000u64),
+                batch_poster: Address::repeat_byte(0x11),
+                batch_num: 42,
+                batch_calldata_length: 100,
+                batch_calldata_non_zeros: 60,
+                batch_extra_gas: 5,
+                l1_base_fee: U256::from(7),
+            }
+        );
+    }
+
+    #[test]
+    fn decode_report_rejects_inconsistent_stats() {
+        let stats = BatchDataStats {
+            length: 10,
+            non_zeros: 11,
+        };
+        assert_eq!(
+            decode_fields_sequencer(&mut &report_payload()[..], 42161, 32, Some(stats), None),
+            Err(SequencerDecodeError::InvalidBatchDataStats {
+                length: 10,
+                non_zeros: 11,
+            })
+        );
+        assert_eq!(
+            decode_fields_sequencer(&mut &report_payload()[..], 42161, 32, None, None),
+            Err(SequencerDecodeError::MissingBatchGasCost)
+        );
+
+        let payload = report_payload();
+        for len in 0..32 * 4 + 20 {
+            assert!(
+                decode_fields_sequencer(&mut &payload[..len], 42161, 32, None, Some(1)).is_err()
+            );
+        }
+    }
+}
@@ -22,7 +22,10 @@ use alloy_rlp::Header;
 use bytes::BufMut;
 use serde::{Deserialize, Serialize};
 
-use crate::transactions::ArbTxType;
+use crate::transactions::{
+    ArbTxType,
+    util::{SequencerDecodeError, decode},
+};
 
 /// Arbitrum L1 ETH deposit transaction (`type = 0x64`).
 #[derive(PartialEq, Debug, Clone, Eq, Serialize, Deserialize)]
@@ -55,14 +58,16 @@ impl TxDeposit {
         keccak256(&buf)
     }
     /// Decodes sequencer feed fields for an ETH deposit payload.
+    ///
+    /// The payload is the packed `(address to, uint256 value)` emitted by the L1 inbox.
     pub fn decode_fields_sequencer(
         buf: &mut &[u8],
         chain_id: U256,
         request_id: FixedBytes<32>,
         from: Address,
-    ) -> Result<Self, alloy_rlp::Error> {
-        let value: U256 = Decodable::decode(buf)?;
-        let to: Address = Decodable::decode(buf)?;
+    ) -> Result<Self, SequencerDecodeError> {
+        let to: Address = decode(buf)?;
+        let value: U256 = decode(buf)?;
         Ok(Self {
             chain_id,
             request_id,
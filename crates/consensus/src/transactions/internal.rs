@@ -1,6 +1,8 @@
 use alloc::vec::Vec;
+use core::fmt;
 
 use alloy_consensus::{Transaction, Typed2718};
+use alloy_core::sol_types::SolCall;
 use alloy_eips::{
     Decodable2718, Encodable2718,
     eip2718::{Eip2718Error, Eip2718Result},
@@ -14,7 +16,11 @@ use alloy_rlp::{Decodable, Encodable, Header};
 use bytes::BufMut;
 use serde::{Deserialize, Serialize};
 
-use crate::transactions::ArbTxType;
+use crate::transactions::{
+    ArbTxType,
+    batchpostingreport::{ArbosActs, BatchPostingReportFields},
+    start_block::StartBlockFields,
+};
 
 /// Arbitrum internal system transaction (ArbOS).
 ///
@@ -94,6 +100,99 @@ impl ArbInternalTx {
     }
 }
 
+/// ArbOS action encoded in an internal transaction's calldata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArbosAction {
+    /// `startBlock`, the first transaction of every L2 block.
+    StartBlock(StartBlockFields),
+    /// Legacy `batchPostingReport` issued before ArbOS 50.
+    BatchPostingReport(BatchPostingReportFields),
+    /// `batchPostingReportV2` issued from ArbOS 50 onwards.
+    BatchPostingReportV2 {
+        /// Batch timestamp recorded in the report.
+        batch_timestamp: U256,
+        /// Address that posted the batch.
+        batch_poster: Address,
+        /// Batch sequence number.
+        batch_num: u64,
+        /// Byte length of the batch calldata.
+        batch_calldata_length: u64,
+        /// Number of non-zero bytes in the batch calldata.
+        batch_calldata_non_zeros: u64,
+        /// Additional gas charged by ArbOS for this batch.
+        batch_extra_gas: u64,
+        /// L1 base fee used for cost accounting.
+        l1_base_fee: U256,
+    },
+}
+
+/// Error while decoding an internal transaction's calldata into an [`ArbosAction`].
+#[derive(Debug)]
+pub enum ArbosActionDecodeError {
+    /// The calldata is shorter than a function selector.
+    MissingSelector,
+    /// The selector does not belong to `ArbosActs`.
+    UnknownSelector(Selector),
+    /// The arguments do not match the selected `ArbosActs` function.
+    Abi(alloy_core::sol_types::Error),
+}
+
+impl fmt::Display for ArbosActionDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSelector => write!(f, "internal tx calldata has no function selector"),
+            Self::UnknownSelector(selector) => {
+                write!(f, "unknown ArbosActs selector {selector}")
+            }
+            Self::Abi(err) => write!(f, "invalid ArbosActs calldata: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ArbosActionDecodeError {}
+
+impl ArbInternalTx {
+    /// Decodes the calldata into the ArbOS action it invokes.
+    pub fn decode_action(&self) -> Result<ArbosAction, ArbosActionDecodeError> {
+        let selector = self
+            .function_selector()
+            .copied()
+            .ok_or(ArbosActionDecodeError::MissingSelector)?;
+        match selector.0 {
+            ArbosActs::startBlockCall::SELECTOR => {
+                let call = ArbosActs::startBlockCall::abi_decode(&self.data)
+                    .map_err(ArbosActionDecodeError::Abi)?;
+                Ok(ArbosAction::StartBlock(call.into()))
+            }
+            ArbosActs::batchPostingReportCall::SELECTOR => {
+                let call = ArbosActs::batchPostingReportCall::abi_decode(&self.data)
+                    .map_err(ArbosActionDecodeError::Abi)?;
+                Ok(ArbosAction::BatchPostingReport(BatchPostingReportFields {
+                    batch_timestamp: call.batchTimestamp,
+                    batch_poster: call.batchPosterAddress,
+                    batch_num: call.batchNumber,
+                    batch_data_gas: call.batchDataGas,
+                    l1_base_fee: call.l1BaseFeeWei,
+                }))
+            }
+            ArbosActs::batchPostingReportV2Call::SELECTOR => {
+                let call = ArbosActs::batchPostingReportV2Call::abi_decode(&self.data)
+                    .map_err(ArbosActionDecodeError::Abi)?;
+                Ok(ArbosAction::BatchPostingReportV2 {
+                    batch_timestamp: call.batchTimestamp,
+                    batch_poster: call.batchPosterAddress,
+                    batch_num: call.batchNumber,
+                    batch_calldata_length: call.batchCalldataLength,
+                    batch_calldata_non_zeros: call.batchCalldataNonZeros,
+                    batch_extra_gas: call.batchExtraGas,
+                    l1_base_fee: call.l1BaseFeeWei,
+                })
+            }
+            _ => Err(ArbosActionDecodeError::UnknownSelector(selector)),
+        }
+    }
+}
+
 impl Typed2718 for ArbInternalTx {
     fn ty(&self) -> u8 {
         ArbTxType::Internal as u8
@@ -223,13 +322,110 @@ impl Sealable for ArbInternalTx {
 
 #[cfg(test)]
 mod tests {
+    use alloy_core::sol_types::SolCall;
     use alloy_eips::Typed2718;
     use alloy_network_primitives::{ReceiptResponse, TransactionResponse};
     use alloy_provider::Provider;
     use serial_test::serial;
     use test_utils::TestContext;
 
-    use super::ArbInternalTx;
+    use super::{ArbInternalTx, ArbosAction, ArbosActionDecodeError, StartBlockFields};
+    use crate::transactions::batchpostingreport::{ArbosActs, BatchPostingReportFields};
+    use alloy_primitives::{Bytes, U256, address};
+
+    #[test]
+    fn decode_action_covers_arbos_acts() {
+        let start = ArbInternalTx::new(
+            42161,
+            ArbosActs::startBlockCall::new((U256::from(7), 100, 200, 3))
+                .abi_encode()
+                .into(),
+        );
+        assert_eq!(
+            start.decode_action().unwrap(),
+            ArbosAction::StartBlock(StartBlockFields {
+                l1_base_fee: U256::from(7),
+                l1_block_number: 100,
+                l2_block_number: 200,
+                time_passed: 3,
+            })
+        );
+
+        let poster = address!("0xa4b000000000000000000073657175656e636572");
+        let report = ArbInternalTx::new(
+            42161,
+            ArbosActs::batchPostingReportCall::new((
+                U256::from(1),
+                poster,
+                9,
+                50_000,
+                U256::from(2),
+            ))
+            .abi_encode()
+            .into(),
+        );
+        assert_eq!(
+            report.decode_action().unwrap(),
+            ArbosAction::BatchPostingReport(BatchPostingReportFields {
+                batch_timestamp: U256::from(1),
+                batch_poster: poster,
+                batch_num: 9,
+                batch_data_gas: 50_000,
+                l1_base_fee: U256::from(2),
+            })
+        );
+
+        let report_v2 = ArbInternalTx::new(
+            42161,
+            ArbosActs::batchPostingReportV2Call::new((
+                U256::from(1),
+                poster,
+                9,
+                1000,
+                600,
+                10,
+                U256::from(2),
+            ))
+            .abi_encode()
+            .into(),
+        );
+        assert_eq!(
+            report_v2.decode_action().unwrap(),
+            ArbosAction::BatchPostingReportV2 {
+                batch_timestamp: U256::from(1),
+                batch_poster: poster,
+                batch_num: 9,
+                batch_calldata_length: 1000,
+                batch_calldata_non_zeros: 600,
+                batch_extra_gas: 10,
+                l1_base_fee: U256::from(2),
+            }
+        );
+    }
+
+    #[test]
+    fn decode_action_rejects_unknown_or_truncated_calldata() {
+        let short = ArbInternalTx::new(42161, Bytes::from_static(&[0x6b, 0xf6]));
+        assert!(matches!(
+            short.decode_action(),
+            Err(ArbosActionDecodeError::MissingSelector)
+        ));
+
+        let unknown = ArbInternalTx::new(42161, Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]));
+        assert!(matches!(
+            unknown.decode_action(),
+            Err(ArbosActionDecodeError::UnknownSelector(selector)) if selector == [0xde, 0xad, 0xbe, 0xef]
+        ));
+
+        let truncated = ArbInternalTx::new(
+            42161,
+            Bytes::copy_from_slice(&ArbosActs::startBlockCall::SELECTOR),
+        );
+        assert!(matches!(
+            truncated.decode_action(),
+            Err(ArbosActionDecodeError::Abi(_))
+        ));
+    }
 
     #[tokio::test]
     #[serial]
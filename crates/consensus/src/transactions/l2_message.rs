@@ -0,0 +1,394 @@
+use alloc::vec::Vec;
+
+use alloy_eips::Decodable2718;
+use alloy_primitives::{Address, B256, Bytes, TxKind, U256, keccak256};
+
+use crate::transactions::{
+    ArbTxEnvelope, TxContract, TxUnsigned,
+    parse_l2::{MAX_L2_MESSAGE_SIZE, ParseL2Error},
+    util::{SequencerDecodeError, decode, decode_address_word, decode_rest, decode_u64},
+};
+
+/// Maximum nesting depth of L2 message batches.
+pub const MAX_L2_MESSAGE_BATCH_DEPTH: usize = 16;
+
+/// First Arbitrum-specific EIP-2718 type, rejected inside signed L2 messages.
+const ARBITRUM_DEPOSIT_TX_TYPE: u8 = 0x64;
+/// EIP-4844 blob transaction type, rejected inside signed L2 messages.
+const BLOB_TX_TYPE: u8 = 0x03;
+
+/// Kind byte that prefixes every L2 message payload.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L2MessageKind {
+    /// Unsigned user transaction with an explicit nonce.
+    UnsignedUserTx = 0,
+    /// Contract transaction keyed by an L1 request id.
+    ContractTx = 1,
+    /// Non-mutating call (unimplemented in Nitro).
+    NonmutatingCall = 2,
+    /// Length-prefixed list of nested L2 messages.
+    Batch = 3,
+    /// EIP-2718 encoded signed transaction.
+    SignedTx = 4,
+    /// Heartbeat message without transactions.
+    Heartbeat = 6,
+    /// Compressed signed transaction (unimplemented in Nitro).
+    SignedCompressedTx = 7,
+}
+
+impl L2MessageKind {
+    /// Converts a raw kind byte into a typed variant.
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::UnsignedUserTx),
+            1 => Some(Self::ContractTx),
+            2 => Some(Self::NonmutatingCall),
+            3 => Some(Self::Batch),
+            4 => Some(Self::SignedTx),
+            6 => Some(Self::Heartbeat),
+            7 => Some(Self::SignedCompressedTx),
+            _ => None,
+        }
+    }
+}
+
+/// Decoded Nitro L2 message.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum L2Message {
+    /// Unsigned user transaction sent by the poster.
+    UnsignedUserTx(TxUnsigned),
+    /// Contract transaction sent by the poster.
+    ContractTx(TxContract),
+    /// Raw non-mutating call payload.
+    NonmutatingCall(Bytes),
+    /// Nested messages in batch order.
+    Batch(Vec<Self>),
+    /// Signed Ethereum transaction.
+    SignedTx(ArbTxEnvelope),
+    /// Heartbeat message.
+    Heartbeat,
+    /// Raw compressed signed transaction payload.
+    SignedCompressedTx(Bytes),
+}
+
+impl L2Message {
+    /// Decodes an L2 message, recursively expanding batches.
+    ///
+    /// Nested messages of a batch derive their request ids as `keccak256(request_id ‖ index)`.
+    ///
+    /// Nitro reference: `arbos/parse_l2.go` -> `parseL2Message`.
+    pub fn decode(
+        buf: &mut &[u8],
+        poster: Address,
+        request_id: Option<B256>,
+        chain_id: U256,
+    ) -> Result<Self, ParseL2Error> {
+        Self::decode_at_depth(buf, poster, request_id, chain_id, 0)
+    }
+
+    fn decode_at_depth(
+        buf: &mut &[u8],
+        poster: Address,
+        request_id: Option<B256>,
+        chain_id: U256,
+        depth: usize,
+    ) -> Result<Self, ParseL2Error> {
+        let (&kind, rest) = buf
+            .split_first()
+            .ok_or(SequencerDecodeError::InputTooShort {
+                needed: 1,
+                remaining: 0,
+            })?;
+        *buf = rest;
+        match L2MessageKind::from_u8(kind) {
+            Some(L2MessageKind::UnsignedUserTx | L2MessageKind::ContractTx) => {
+                Self::decode_unsigned(buf, kind, poster, request_id, chain_id)
+            }
+            Some(L2MessageKind::NonmutatingCall) => Ok(Self::NonmutatingCall(decode_rest(buf))),
+            Some(L2MessageKind::Batch) => {
+                if depth >= MAX_L2_MESSAGE_BATCH_DEPTH {
+                    return Err(ParseL2Error::BatchTooDeep);
+                }
+                let mut messages = Vec::new();
+                // A segment that cannot be read ends the batch, as in Nitro.
+                while let Some(mut segment) = next_segment(buf) {
+                    let index = U256::from(messages.len());
+                    let sub_request_id =
+                        request_id.map(|id| keccak256([id.0, index.to_be_bytes()].concat()));
+                    messages.push(Self::decode_at_depth(
+                        &mut segment,
+                        poster,
+                        sub_request_id,
+                        chain_id,
+                        depth + 1,
+                    )?);
+                }
+                Ok(Self::Batch(messages))
+            }
+            Some(L2MessageKind::SignedTx) => decode_signed_tx(buf).map(Self::SignedTx),
+            Some(L2MessageKind::Heartbeat) => Ok(Self::Heartbeat),
+            Some(L2MessageKind::SignedCompressedTx) => {
+                Ok(Self::SignedCompressedTx(decode_rest(buf)))
+            }
+            None => Err(ParseL2Error::UnsupportedL2MessageKind(kind)),
+        }
+    }
+
+    /// Decodes an unsigned user or contract transaction from its packed 32-byte fields.
+    ///
+    /// Nitro reference: `arbos/parse_l2.go` -> `parseUnsignedTx`.
+    pub(crate) fn decode_unsigned(
+        buf: &mut &[u8],
+        kind: u8,
+        poster: Address,
+        request_id: Option<B256>,
+        chain_id: U256,
+    ) -> Result<Self, ParseL2Error> {
+        let gas_limit = decode_u64(buf, "gas_limit")?;
+        let gas_fee_cap: U256 = decode(buf)?;
+        let nonce = if kind == L2MessageKind::UnsignedUserTx as u8 {
+            decode_u64(buf, "nonce")?
+        } else {
+            0
+        };
+        let to = decode_address_word(buf)?;
+        let to = if to.is_zero() {
+            TxKind::Create
+        } else {
+            TxKind::Call(to)
+        };
+        let value: U256 = decode(buf)?;
+        let input = decode_rest(buf);
+
+        match L2MessageKind::from_u8(kind) {
+            Some(L2MessageKind::UnsignedUserTx) => Ok(Self::UnsignedUserTx(TxUnsigned {
+                chain_id,
+                from: poster,
+                nonce,
+                gas_fee_cap,
+                gas_limit,
+                to,
+                value,
+                input,
+            })),
+            Some(L2MessageKind::ContractTx) => Ok(Self::ContractTx(TxContract {
+                chain_id,
+                request_id: request_id.ok_or(ParseL2Error::MissingRequestId)?,
+                from: poster,
+                gas_fee_cap,
+                gas_limit,
+                to,
+                value,
+                input,
+            })),
+            _ => Err(ParseL2Error::UnsupportedL2MessageKind(kind)),
+        }
+    }
+
+    /// Returns the kind byte of this message.
+    pub const fn kind(&self) -> L2MessageKind {
+        match self {
+            Self::UnsignedUserTx(_) => L2MessageKind::UnsignedUserTx,
+            Self::ContractTx(_) => L2MessageKind::ContractTx,
+            Self::NonmutatingCall(_) => L2MessageKind::NonmutatingCall,
+            Self::Batch(_) => L2MessageKind::Batch,
+            Self::SignedTx(_) => L2MessageKind::SignedTx,
+            Self::Heartbeat => L2MessageKind::Heartbeat,
+            Self::SignedCompressedTx(_) => L2MessageKind::SignedCompressedTx,
+        }
+    }
+
+    /// Flattens the message into transactions in execution order.
+    ///
+    /// Non-mutating calls and compressed transactions fail, as Nitro does not implement them.
+    pub fn into_transactions(self) -> Result<Vec<ArbTxEnvelope>, ParseL2Error> {
+        let mut txs = Vec::new();
+        self.collect_transactions(&mut txs)?;
+        Ok(txs)
+    }
+
+    fn collect_transactions(self, txs: &mut Vec<ArbTxEnvelope>) -> Result<(), ParseL2Error> {
+        match self {
+            Self::UnsignedUserTx(tx) => txs.push(tx.into()),
+            Self::ContractTx(tx) => txs.push(tx.into()),
+            Self::SignedTx(tx) => txs.push(tx),
+            Self::Batch(messages) => {
+                for message in messages {
+                    message.collect_transactions(txs)?;
+                }
+            }
+            Self::Heartbeat => {}
+            Self::NonmutatingCall(_) | Self::SignedCompressedTx(_) => {
+                return Err(ParseL2Error::UnsupportedL2MessageKind(self.kind() as u8));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the next `u64` length-prefixed batch segment, or `None` when the batch ends.
+///
+/// Nitro reference: `arbos/util/util.go` -> `BytestringFromReader`.
+fn next_segment<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let (len, rest) = buf.split_first_chunk::<8>()?;
+    let len = usize::try_from(u64::from_be_bytes(*len)).ok()?;
+    if len > MAX_L2_MESSAGE_SIZE || rest.len() < len {
+        return None;
+    }
+    let (segment, rest) = rest.split_at(len);
+    *buf = rest;
+    Some(segment)
+}
+
+/// Decodes a signed Ethereum transaction, rejecting Arbitrum and blob types.
+fn decode_signed_tx(buf: &mut &[u8]) -> Result<ArbTxEnvelope, ParseL2Error> {
+    if let Some(&ty) = buf.first()
+        && ((ARBITRUM_DEPOSIT_TX_TYPE..0x80).contains(&ty) || ty == BLOB_TX_TYPE)
+    {
+        return Err(ParseL2Error::UnsupportedTxType(ty));
+    }
+    let tx = ArbTxEnvelope::decode_2718(buf)?;
+    if !buf.is_empty() {
+        return Err(ParseL2Error::Decode(alloy_rlp::Error::UnexpectedLength));
+    }
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Signed, TxLegacy};
+    use alloy_eips::Encodable2718;
+    use alloy_primitives::{Signature, address};
+
+    const POSTER: Address = address!("0x1111111111111111111111111111111111111111");
+
+    fn chain_id() -> U256 {
+        U256::from(42161)
+    }
+
+    fn segment(payload: &[u8]) -> Vec<u8> {
+        let mut out = (payload.len() as u64).to_be_bytes().to_vec();
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn contract_tx(value: u64) -> Vec<u8> {
+        let mut out = vec![L2MessageKind::ContractTx as u8];
+        out.extend_from_slice(&U256::from(50000).to_be_bytes::<32>());
+        out.extend_from_slice(&U256::from(100).to_be_bytes::<32>());
+        out.extend_from_slice(&[0u8; 32]);
+        out.extend_from_slice(&U256::from(value).to_be_bytes::<32>());
+        out
+    }
+
+    fn signed_tx() -> Vec<u8> {
+        let tx = TxLegacy {
+            chain_id: Some(42161),
+            nonce: 1,
+            gas_price: 100,
+            gas_limit: 21000,
+            to: TxKind::Call(POSTER),
+            ..Default::default()
+        };
+        let signed = Signed::new_unhashed(tx, Signature::new(U256::ONE, U256::ONE, false));
+        let mut out = vec![L2MessageKind::SignedTx as u8];
+        ArbTxEnvelope::Legacy(signed).encode_2718(&mut out);
+        out
+    }
+
+    fn batch(segments: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = vec![L2MessageKind::Batch as u8];
+        for payload in segments {
+            out.extend_from_slice(&segment(payload));
+        }
+        out
+    }
+
+    #[test]
+    fn nested_batches_expand_with_derived_request_ids() {
+        let request_id = B256::from([0xaa; 32]);
+        let inner = batch(&[contract_tx(2), vec![L2MessageKind::Heartbeat as u8]]);
+        let payload = batch(&[contract_tx(1), inner, signed_tx()]);
+
+        let message = L2Message::decode(
+            &mut payload.as_slice(),
+            POSTER,
+            Some(request_id),
+            chain_id(),
+        )
+        .unwrap();
+        assert_eq!(message.kind(), L2MessageKind::Batch);
+        let txs = message.into_transactions().unwrap();
+        assert_eq!(txs.len(), 3);
+
+        let first = keccak256([request_id.0, [0u8; 32]].concat());
+        let inner_id = keccak256([request_id.0, U256::ONE.to_be_bytes()].concat());
+        let second = keccak256([inner_id.0, [0u8; 32]].concat());
+        let [
+            ArbTxEnvelope::Contract(a),
+            ArbTxEnvelope::Contract(b),
+            ArbTxEnvelope::Legacy(_),
+        ] = txs.as_slice()
+        else {
+            panic!("unexpected transactions {txs:?}");
+        };
+        assert_eq!((a.request_id, a.value), (first, U256::from(1)));
+        assert_eq!((b.request_id, b.value), (second, U256::from(2)));
+    }
+
+    #[test]
+    fn batch_depth_is_limited() {
+        let mut payload = vec![L2MessageKind::Heartbeat as u8];
+        for _ in 0..MAX_L2_MESSAGE_BATCH_DEPTH {
+            payload = batch(&[payload]);
+        }
+        assert!(L2Message::decode(&mut payload.as_slice(), POSTER, None, chain_id()).is_ok());
+
+        let payload = batch(&[payload]);
+        assert_eq!(
+            L2Message::decode(&mut payload.as_slice(), POSTER, None, chain_id()).unwrap_err(),
+            ParseL2Error::BatchTooDeep
+        );
+    }
+
+    #[test]
+    fn unreadable_segment_ends_batch() {
+        let mut payload = batch(&[signed_tx()]);
+        payload.extend_from_slice(&((MAX_L2_MESSAGE_SIZE as u64) + 1).to_be_bytes());
+        payload.extend_from_slice(&[0u8; 16]);
+
+        let message = L2Message::decode(&mut payload.as_slice(), POSTER, None, chain_id()).unwrap();
+        assert_eq!(message.into_transactions().unwrap().len(), 1);
+
+        let mut truncated = batch(&[signed_tx()]);
+        truncated.truncate(truncated.len() - 1);
+        let message =
+            L2Message::decode(&mut truncated.as_slice(), POSTER, None, chain_id()).unwrap();
+        assert!(message.into_transactions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unimplemented_kinds_do_not_produce_transactions() {
+        for kind in [
+            L2MessageKind::NonmutatingCall,
+            L2MessageKind::SignedCompressedTx,
+        ] {
+            let payload = batch(&[vec![kind as u8, 0x01, 0x02]]);
+            let message =
+                L2Message::decode(&mut payload.as_slice(), POSTER, None, chain_id()).unwrap();
+            assert_eq!(
+                message.into_transactions().unwrap_err(),
+                ParseL2Error::UnsupportedL2MessageKind(kind as u8)
+            );
+        }
+
+        assert_eq!(
+            L2Message::decode(&mut [5u8].as_slice(), POSTER, None, chain_id()).unwrap_err(),
+            ParseL2Error::UnsupportedL2MessageKind(5)
+        );
+    }
+}
@@ -5,13 +5,16 @@ use alloy_consensus::{
     transaction::TxHashRef,
 };
 use alloy_primitives::{Address, Sealable, TxHash};
+pub use arbitrum_legacy::TxArbitrumLegacy;
 pub use contract::TxContract;
 pub use deposit::TxDeposit;
 pub use retry::TxRetry;
 pub use unsigned::TxUnsigned;
 
 use crate::transactions::{internal::ArbInternalTx, submit_retryable::SubmitRetryableTx};
-/// Batch posting report decoder utilities. Unused at the moment, remainder from sequencer reader.
+/// Classic Arbitrum legacy transaction type (`0x78`).
+pub mod arbitrum_legacy;
+/// Batch posting report decoder utilities.
 pub mod batchpostingreport;
 /// Arbitrum contract transaction type (`0x66`).
 pub mod contract;
@@ -19,8 +22,14 @@ pub mod contract;
 pub mod deposit;
 /// Arbitrum internal system transaction type (`0x6a`).
 pub mod internal;
+/// Typed Nitro L2 message payloads.
+pub mod l2_message;
+/// Decoding of L1 incoming messages into transactions.
+pub mod parse_l2;
 /// Arbitrum retry transaction type (`0x68`).
 pub mod retry;
+/// ArbOS `startBlock` internal transaction builder.
+pub mod start_block;
 /// Arbitrum submit-retryable transaction type (`0x69`).
 pub mod submit_retryable;
 /// Typed unsigned transaction enum used by builders.
@@ -67,6 +76,9 @@ pub enum ArbTxEnvelope {
     /// Arbitrum internal system transaction.
     #[envelope(ty = 0x6a)]
     Internal(Sealed<ArbInternalTx>),
+    /// Classic (pre-Nitro) Arbitrum transaction.
+    #[envelope(ty = 0x78)]
+    ArbitrumLegacy(TxArbitrumLegacy),
 }
 
 impl ArbTxEnvelope {
@@ -83,6 +95,7 @@ impl ArbTxEnvelope {
             Self::Contract(tx) => tx.hash_ref(),
             Self::Retry(tx) => tx.hash_ref(),
             Self::Internal(tx) => tx.hash_ref(),
+            Self::ArbitrumLegacy(tx) => &tx.hash_override,
         }
     }
 
@@ -96,6 +109,21 @@ impl ArbTxEnvelope {
         self.hash()
     }
 
+    /// Returns the unaliased L1 address that originated an L1-to-L2 transaction.
+    ///
+    /// Unsigned, contract, retry and submit-retryable transactions carry the aliased L1
+    /// sender as `from`; other variants return `None`.
+    pub fn l1_origin(&self) -> Option<Address> {
+        let aliased = match self {
+            Self::SubmitRetryable(tx) => tx.from(),
+            Self::Unsigned(tx) => tx.from(),
+            Self::Contract(tx) => tx.from(),
+            Self::Retry(tx) => tx.from(),
+            _ => return None,
+        };
+        Some(crate::alias::undo_l1_to_l2_alias(aliased))
+    }
+
     /// Recover the sender address.
     pub fn sender(&self) -> Result<Address, alloy_primitives::SignatureError> {
         match self {
@@ -157,6 +185,7 @@ impl ArbTxEnvelope {
             Self::Contract(tx) => Ok(tx.from()),
             Self::Retry(tx) => Ok(tx.from()),
             Self::Internal(tx) => Ok(tx.from()),
+            Self::ArbitrumLegacy(tx) => tx.sender(),
         }
     }
 }
@@ -193,6 +222,9 @@ impl alloy_consensus::transaction::SignerRecoverable for ArbTxEnvelope {
             Self::Contract(tx) => Ok(tx.from()),
             Self::Retry(tx) => Ok(tx.from()),
             Self::Internal(tx) => Ok(tx.from()),
+            Self::ArbitrumLegacy(tx) => tx
+                .sender()
+                .map_err(alloy_consensus::crypto::RecoveryError::from_source),
         }
     }
 
@@ -220,6 +252,9 @@ impl alloy_consensus::transaction::SignerRecoverable for ArbTxEnvelope {
             Self::Contract(tx) => Ok(tx.from()),
             Self::Retry(tx) => Ok(tx.from()),
             Self::Internal(tx) => Ok(tx.from()),
+            Self::ArbitrumLegacy(tx) => tx
+                .sender()
+                .map_err(alloy_consensus::crypto::RecoveryError::from_source),
         }
     }
 
@@ -231,6 +266,12 @@ impl alloy_consensus::transaction::SignerRecoverable for ArbTxEnvelope {
     }
 }
 
+impl From<TxArbitrumLegacy> for ArbTxEnvelope {
+    fn from(tx: TxArbitrumLegacy) -> Self {
+        Self::ArbitrumLegacy(tx)
+    }
+}
+
 impl From<ArbInternalTx> for ArbTxEnvelope {
     fn from(tx: ArbInternalTx) -> Self {
         Self::Internal(tx.seal_slow())
@@ -295,6 +336,7 @@ impl Display for ArbTxType {
             Self::Contract => write!(f, "Contract"),
             Self::Retry => write!(f, "Retry"),
             Self::Internal => write!(f, "Internal"),
+            Self::ArbitrumLegacy => write!(f, "ArbitrumLegacy"),
         }
     }
 }
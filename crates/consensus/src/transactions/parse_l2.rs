@@ -0,0 +1,397 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use alloy_consensus::Transaction;
+use alloy_eips::eip2718::Eip2718Error;
+use alloy_primitives::{Address, ChainId, U256, keccak256};
+use arb_sequencer_network::sequencer::feed::{
+    FeedDecodeError, L1Header, MessageType, MessageWithMetadata,
+};
+
+use crate::transactions::{
+    ArbTxEnvelope, SubmitRetryableTx, TxDeposit, batchpostingreport,
+    l2_message::{L2Message, MAX_L2_MESSAGE_BATCH_DEPTH},
+    util::SequencerDecodeError,
+};
+
+/// Maximum accepted `l2Msg` payload size in bytes.
+pub const MAX_L2_MESSAGE_SIZE: usize = 256 * 1024;
+
+/// Error while turning an L1 incoming message into transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseL2Error {
+    /// The feed header or `l2Msg` payload could not be decoded.
+    Feed(FeedDecodeError),
+    /// The `l2Msg` payload exceeds [`MAX_L2_MESSAGE_SIZE`].
+    MessageTooLarge {
+        /// Payload size in bytes.
+        size: usize,
+    },
+    /// The L1 message kind does not produce transactions.
+    UnsupportedMessageKind(u8),
+    /// The L2 message kind is unknown or not implemented.
+    UnsupportedL2MessageKind(u8),
+    /// L2 message batches are nested deeper than [`MAX_L2_MESSAGE_BATCH_DEPTH`].
+    BatchTooDeep,
+    /// A signed transaction used a type that is not allowed in L2 messages.
+    UnsupportedTxType(u8),
+    /// The message kind requires an L1 request id but the header has none.
+    MissingRequestId,
+    /// A packed payload field was truncated or inconsistent.
+    Field(SequencerDecodeError),
+    /// A signed transaction was not valid RLP.
+    Decode(alloy_rlp::Error),
+}
+
+impl fmt::Display for ParseL2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feed(err) => write!(f, "invalid feed message: {err}"),
+            Self::MessageTooLarge { size } => {
+                write!(
+                    f,
+                    "L2 message too large: got {size} bytes, max {MAX_L2_MESSAGE_SIZE}"
+                )
+            }
+            Self::UnsupportedMessageKind(kind) => {
+                write!(f, "L1 message kind {kind} does not produce transactions")
+            }
+            Self::UnsupportedL2MessageKind(kind) => {
+                write!(f, "unsupported L2 message kind {kind}")
+            }
+            Self::BatchTooDeep => {
+                write!(
+                    f,
+                    "L2 message batches have a max depth of {MAX_L2_MESSAGE_BATCH_DEPTH}"
+                )
+            }
+            Self::UnsupportedTxType(ty) => {
+                write!(
+                    f,
+                    "transaction type {ty:#04x} is not allowed in L2 messages"
+                )
+            }
+            Self::MissingRequestId => write!(f, "message requires an L1 request id"),
+            Self::Field(err) => write!(f, "failed to decode L2 message: {err}"),
+            Self::Decode(err) => write!(f, "failed to decode signed transaction: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseL2Error {}
+
+impl From<FeedDecodeError> for ParseL2Error {
+    fn from(err: FeedDecodeError) -> Self {
+        Self::Feed(err)
+    }
+}
+
+impl From<SequencerDecodeError> for ParseL2Error {
+    fn from(err: SequencerDecodeError) -> Self {
+        Self::Field(err)
+    }
+}
+
+impl From<alloy_rlp::Error> for ParseL2Error {
+    fn from(err: alloy_rlp::Error) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<Eip2718Error> for ParseL2Error {
+    fn from(err: Eip2718Error) -> Self {
+        match err {
+            Eip2718Error::RlpError(err) => Self::Decode(err),
+            Eip2718Error::UnexpectedType(ty) => Self::UnsupportedTxType(ty),
+            _ => Self::Decode(alloy_rlp::Error::Custom("invalid EIP-2718 envelope")),
+        }
+    }
+}
+
+/// Decodes the transactions carried by a sequencer feed message.
+///
+/// Request ids, the poster and the L1 base fee are taken from the message's [`L1Header`];
+/// `arbos_version` selects the batch posting report encoding.
+///
+/// Nitro reference: `arbos/parse_l2.go` -> `ParseL2Transactions`.
+pub fn parse_l2_transactions(
+    msg: &MessageWithMetadata,
+    chain_id: ChainId,
+    arbos_version: u64,
+) -> Result<Vec<ArbTxEnvelope>, ParseL2Error> {
+    let incoming = &msg.l1_incoming_message;
+    let header = L1Header::from_header(&incoming.header, msg.delayed_messages_read)?;
+    let l2msg = incoming.l2msg_bytes()?;
+    if l2msg.len() > MAX_L2_MESSAGE_SIZE {
+        return Err(ParseL2Error::MessageTooLarge { size: l2msg.len() });
+    }
+    let buf = &mut l2msg.as_slice();
+    let chain_id_u256 = U256::from(chain_id);
+
+    match MessageType::from_u8(header.kind) {
+        MessageType::L2Message => {
+            L2Message::decode(buf, header.poster, header.request_id, chain_id_u256)?
+                .into_transactions()
+        }
+        MessageType::EndOfBlock | MessageType::RollupEvent => Ok(Vec::new()),
+        MessageType::L2FundedByL1 => {
+            let request_id = header.request_id.ok_or(ParseL2Error::MissingRequestId)?;
+            let (&kind, rest) = buf
+                .split_first()
+                .ok_or(SequencerDecodeError::InputTooShort {
+                    needed: 1,
+                    remaining: 0,
+                })?;
+            let deposit_request_id = keccak256([request_id.0, U256::ZERO.to_be_bytes()].concat());
+            let unsigned_request_id =
+                keccak256([request_id.0, U256::from(1).to_be_bytes()].concat());
+            let txs = L2Message::decode_unsigned(
+                &mut &rest[..],
+                kind,
+                header.poster,
+                Some(unsigned_request_id),
+                chain_id_u256,
+            )?
+            .into_transactions()?;
+            let deposit = TxDeposit {
+                chain_id: chain_id_u256,
+                request_id: deposit_request_id,
+                from: Address::ZERO,
+                to: header.poster,
+                value: txs.iter().map(Transaction::value).sum(),
+            };
+            Ok(core::iter::once(deposit.into()).chain(txs).collect())
+        }
+        MessageType::SubmitRetryable => {
+            let request_id = header.request_id.ok_or(ParseL2Error::MissingRequestId)?;
+            // Nitro treats a missing L1 base fee as zero.
+            let l1_base_fee = header.base_fee_l1.unwrap_or_default();
+            let tx = SubmitRetryableTx::decode_fields_sequencer(
+                buf,
+                chain_id_u256,
+                request_id,
+                header.poster,
+                l1_base_fee,
+            )?;
+            Ok(vec![tx.into()])
+        }
+        MessageType::EthDeposit => {
+            let request_id = header.request_id.ok_or(ParseL2Error::MissingRequestId)?;
+            let tx =
+                TxDeposit::decode_fields_sequencer(buf, chain_id_u256, request_id, header.poster)?;
+            Ok(vec![tx.into()])
+        }
+        MessageType::BatchPostingReport => {
+            let tx = batchpostingreport::decode_fields_sequencer(
+                buf,
+                chain_id,
+                arbos_version,
+                incoming.batch_data_stats.clone(),
+                incoming.legacy_batch_gas_cost,
+            )?;
+            Ok(vec![tx.into()])
+        }
+        MessageType::BatchForGasEstimation | MessageType::Initialize | MessageType::Invalid => {
+            Err(ParseL2Error::UnsupportedMessageKind(header.kind))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions::{TxUnsigned, l2_message::L2MessageKind};
+    use alloy_eips::Encodable2718;
+    use alloy_primitives::{B256, TxHash, TxKind, address, hex, hex::FromHex};
+    use arb_sequencer_network::sequencer::feed::{Header, L1IncomingMessage};
+    use serde_json::{Value, json};
+
+    const CHAIN_ID: ChainId = 42161;
+
+    fn message(
+        kind: u8,
+        sender: Address,
+        request_id: Value,
+        base_fee: Value,
+        l2msg: &[u8],
+    ) -> MessageWithMetadata {
+        MessageWithMetadata {
+            l1_incoming_message: L1IncomingMessage {
+                header: Header {
+                    kind,
+                    sender: sender.to_string(),
+                    block_number: 1,
+                    timestamp: 1,
+                    request_id,
+                    base_fee_l1: base_fee,
+                },
+                l2msg: format!("0x{}", hex::encode(l2msg)),
+                legacy_batch_gas_cost: None,
+                batch_data_stats: None,
+            },
+            delayed_messages_read: 1,
+        }
+    }
+
+    fn word(value: u64) -> [u8; 32] {
+        U256::from(value).to_be_bytes()
+    }
+
+    #[test]
+    fn eth_deposit_matches_nitro_hash() {
+        let mut l2msg = address!("0x2222222222222222222222222222222222222222").to_vec();
+        l2msg.extend_from_slice(&word(123));
+        let msg = message(
+            12,
+            address!("0x1111111111111111111111111111111111111111"),
+            json!(B256::from([0xcc; 32]).to_string()),
+            Value::Null,
+            &l2msg,
+        );
+
+        let txs = parse_l2_transactions(&msg, CHAIN_ID, 32).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(
+            txs[0].hash(),
+            TxHash::from_hex("0xd53dd73b1b2534d4023508fa3c26f4c7ea74b923c0f190a8874dd689a2c1cd21")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn unsigned_l2_message_matches_nitro_hash() {
+        let mut l2msg = vec![L2MessageKind::UnsignedUserTx as u8];
+        l2msg.extend_from_slice(&word(21000));
+        l2msg.extend_from_slice(&word(12345));
+        l2msg.extend_from_slice(&word(7));
+        l2msg.extend_from_slice(
+            address!("0x2222222222222222222222222222222222222222")
+                .into_word()
+                .as_slice(),
+        );
+        l2msg.extend_from_slice(&word(100));
+        l2msg.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        let msg = message(
+            3,
+            address!("0x1111111111111111111111111111111111111111"),
+            Value::Null,
+            Value::Null,
+            &l2msg,
+        );
+
+        let txs = parse_l2_transactions(&msg, CHAIN_ID, 32).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(
+            txs[0].hash(),
+            TxHash::from_hex("0x815fe243a019e4903168fa746b13bc64eeb677d3f9f87ce40ff8ce95fe25eae4")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn submit_retryable_uses_header_fields() {
+        let l2msg = hex::decode(
+            "000000000000000000000000abc50aee89c1b38d4ddc4ac0aee43647215ff7fc000000000000000000000000000000000000000000000000002382664887b00000000000000000000000000000000000000000000000000000239debfd13ec00000000000000000000000000000000000000000000000000000001bdcb71f400000000000000000000000000abc50aee89c1b38d4ddc4ac0aee43647215ff7fc000000000000000000000000abc50aee89c1b38d4ddc4ac0aee43647215ff7fc00000000000000000000000000000000000000000000000000000000000493e00000000000000000000000000000000000000000000000000000000005a1c5c00000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let msg = message(
+            9,
+            address!("0x8789dfc2406ac2d60f174813e8a79f2b69862566"),
+            json!(B256::from(U256::from(0x20eb40)).to_string()),
+            json!(335396856u64),
+            &l2msg,
+        );
+
+        let txs = parse_l2_transactions(&msg, CHAIN_ID, 32).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(
+            txs[0].hash(),
+            TxHash::from_hex("0x19f98fc86cae7ac924a2ad789e86fca824aff065ec7366daedeb1d8e60ae96f5")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn l2_funded_by_l1_prepends_deposit() {
+        let poster = address!("0x1111111111111111111111111111111111111111");
+        let request_id = B256::from([0xaa; 32]);
+        let mut l2msg = vec![L2MessageKind::ContractTx as u8];
+        l2msg.extend_from_slice(&word(50000));
+        l2msg.extend_from_slice(&word(12345));
+        l2msg.extend_from_slice(&[0u8; 32]);
+        l2msg.extend_from_slice(&word(9));
+        let msg = message(
+            7,
+            poster,
+            json!(request_id.to_string()),
+            Value::Null,
+            &l2msg,
+        );
+
+        let txs = parse_l2_transactions(&msg, CHAIN_ID, 32).unwrap();
+        let [
+            ArbTxEnvelope::Deposit(deposit),
+            ArbTxEnvelope::Contract(contract),
+        ] = txs.as_slice()
+        else {
+            panic!("expected deposit followed by contract tx, got {txs:?}");
+        };
+        assert_eq!(
+            deposit.request_id,
+            keccak256([request_id.0, [0u8; 32]].concat())
+        );
+        assert_eq!(deposit.from, Address::ZERO);
+        assert_eq!(deposit.to, poster);
+        assert_eq!(deposit.value, U256::from(9));
+        assert_eq!(
+            contract.request_id,
+            keccak256([request_id.0, word(1)].concat())
+        );
+        assert_eq!(contract.to, TxKind::Create);
+        assert_eq!(contract.gas_limit, 50000);
+    }
+
+    #[test]
+    fn signed_tx_rejects_arbitrum_types() {
+        let unsigned = TxUnsigned {
+            chain_id: U256::from(CHAIN_ID),
+            from: Address::ZERO,
+            nonce: 0,
+            gas_fee_cap: U256::ZERO,
+            gas_limit: 0,
+            to: TxKind::Create,
+            value: U256::ZERO,
+            input: Default::default(),
+        };
+        let mut l2msg = vec![L2MessageKind::SignedTx as u8];
+        unsigned.encode_2718(&mut l2msg);
+        let msg = message(3, Address::ZERO, Value::Null, Value::Null, &l2msg);
+
+        assert_eq!(
+            parse_l2_transactions(&msg, CHAIN_ID, 32).unwrap_err(),
+            ParseL2Error::UnsupportedTxType(0x65)
+        );
+    }
+
+    #[test]
+    fn message_kinds_without_transactions() {
+        let end_of_block = message(6, Address::ZERO, Value::Null, Value::Null, &[]);
+        assert!(
+            parse_l2_transactions(&end_of_block, CHAIN_ID, 32)
+                .unwrap()
+                .is_empty()
+        );
+
+        let initialize = message(11, Address::ZERO, Value::Null, Value::Null, &[]);
+        assert_eq!(
+            parse_l2_transactions(&initialize, CHAIN_ID, 32).unwrap_err(),
+            ParseL2Error::UnsupportedMessageKind(11)
+        );
+
+        let deposit = message(12, Address::ZERO, Value::Null, Value::Null, &[0u8; 52]);
+        assert_eq!(
+            parse_l2_transactions(&deposit, CHAIN_ID, 32).unwrap_err(),
+            ParseL2Error::MissingRequestId
+        );
+    }
+}
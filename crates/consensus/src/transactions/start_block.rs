@@ -0,0 +1,134 @@
+use alloy_consensus::BlockHeader;
+use alloy_core::sol_types::SolCall;
+use alloy_primitives::{ChainId, U256};
+use arb_sequencer_network::sequencer::feed::L1Header;
+use serde::{Deserialize, Serialize};
+
+use crate::transactions::{batchpostingreport::ArbosActs, internal::ArbInternalTx};
+
+// Nitro reference
+// - arbos/block_processor.go:
+//   - createNewHeader() clamps the block timestamp to the parent's timestamp
+//   - ProduceBlockAdvanced() prepends InternalTxStartBlock() to every block
+// - arbos/internal_tx.go:
+//   - InternalTxStartBlock() packs `startBlock(l1BaseFee, l1BlockNum, l2BlockNum, timePassed)`
+
+/// Arguments of the ArbOS `startBlock` internal call that opens every L2 block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartBlockFields {
+    /// L1 base fee reported by the message header (zero when absent).
+    pub l1_base_fee: U256,
+    /// L1 block number of the message.
+    pub l1_block_number: u64,
+    /// Number of the L2 block being opened.
+    pub l2_block_number: u64,
+    /// Seconds elapsed since the parent block.
+    pub time_passed: u64,
+}
+
+impl StartBlockFields {
+    /// Derives the `startBlock` arguments for the block built from `header` on top of `parent`.
+    pub fn from_l1_header<H: BlockHeader>(header: &L1Header, parent: &H) -> Self {
+        let timestamp = header.timestamp.max(parent.timestamp());
+        Self {
+            l1_base_fee: header.base_fee_l1.unwrap_or_default(),
+            l1_block_number: header.block_number,
+            l2_block_number: parent.number() + 1,
+            time_passed: timestamp - parent.timestamp(),
+        }
+    }
+
+    /// Builds the `startBlock` internal transaction for these arguments.
+    pub fn into_internal_tx(self, chain_id: ChainId) -> ArbInternalTx {
+        let data = ArbosActs::startBlockCall::from(self).abi_encode();
+        ArbInternalTx::new(chain_id, data.into())
+    }
+}
+
+impl From<StartBlockFields> for ArbosActs::startBlockCall {
+    fn from(fields: StartBlockFields) -> Self {
+        Self::new((
+            fields.l1_base_fee,
+            fields.l1_block_number,
+            fields.l2_block_number,
+            fields.time_passed,
+        ))
+    }
+}
+
+impl From<ArbosActs::startBlockCall> for StartBlockFields {
+    fn from(call: ArbosActs::startBlockCall) -> Self {
+        Self {
+            l1_base_fee: call.l1BaseFee,
+            l1_block_number: call.l1BlockNumber,
+            l2_block_number: call.l2BlockNumber,
+            time_passed: call.timePassed,
+        }
+    }
+}
+
+impl ArbInternalTx {
+    /// Builds the `startBlock` internal transaction that opens the block following `parent`.
+    pub fn start_block<H: BlockHeader>(chain_id: ChainId, header: &L1Header, parent: &H) -> Self {
+        StartBlockFields::from_l1_header(header, parent).into_internal_tx(chain_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, hex};
+
+    fn l1_header(timestamp: u64, base_fee_l1: Option<U256>) -> L1Header {
+        L1Header {
+            kind: 3,
+            block_number: 20_000_000,
+            timestamp,
+            request_id: None,
+            base_fee_l1,
+            poster: Address::ZERO,
+            delayed_messages_read: 1,
+        }
+    }
+
+    fn parent(number: u64, timestamp: u64) -> Header {
+        Header {
+            number,
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn start_block_encodes_arbos_acts_call() {
+        let header = l1_header(1_700_000_012, Some(U256::from(30_000_000_000u64)));
+        let tx = ArbInternalTx::start_block(42161, &header, &parent(99, 1_700_000_000));
+
+        assert_eq!(tx.chain_id, 42161);
+        assert_eq!(
+            tx.data[..],
+            hex!(
+                "6bf6a42d"
+                "00000000000000000000000000000000000000000000000000000006fc23ac00"
+                "0000000000000000000000000000000000000000000000000000000001312d00"
+                "0000000000000000000000000000000000000000000000000000000000000064"
+                "000000000000000000000000000000000000000000000000000000000000000c"
+            )
+        );
+    }
+
+    #[test]
+    fn start_block_clamps_timestamp_and_defaults_base_fee() {
+        let fields = StartBlockFields::from_l1_header(&l1_header(90, None), &parent(7, 100));
+        assert_eq!(
+            fields,
+            StartBlockFields {
+                l1_base_fee: U256::ZERO,
+                l1_block_number: 20_000_000,
+                l2_block_number: 8,
+                time_passed: 0,
+            }
+        );
+    }
+}
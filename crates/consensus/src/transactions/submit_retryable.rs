@@ -2,6 +2,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 
 use alloy_consensus::{Transaction, Typed2718};
+use alloy_core::sol_types::SolCall;
 use alloy_eips::{
     Decodable2718, Encodable2718,
     eip2718::{Eip2718Error, Eip2718Result},
@@ -12,13 +13,36 @@ use alloy_primitives::{
     Address, B256, Bytes, ChainId, FixedBytes, Sealable, TxHash, TxKind, U256, address, keccak256,
 };
 use alloy_rlp::{BufMut, Decodable, Encodable, Header};
-use bytes::Buf;
+use core::fmt;
 use serde::{Deserialize, Serialize};
 
 use crate::transactions::{
     ArbTxType,
-    util::{decode, decode_rest},
+    parse_l2::MAX_L2_MESSAGE_SIZE,
+    util::{SequencerDecodeError, decode, decode_address_word, decode_bytes, decode_u64},
 };
+mod abi {
+    #![allow(clippy::too_many_arguments, missing_docs)]
+
+    alloy_core::sol! {
+        interface ArbRetryableTx {
+            function submitRetryable(
+                bytes32 requestId,
+                uint256 l1BaseFee,
+                uint256 deposit,
+                uint256 callvalue,
+                uint256 gasFeeCap,
+                uint64 gasLimit,
+                uint256 maxSubmissionFee,
+                address feeRefundAddress,
+                address beneficiary,
+                address retryTo,
+                bytes retryData
+            ) external;
+        }
+    }
+}
+
 /// <https://github.com/OffchainLabs/nitro/blob/23cae22e1f76cf3675f965d78e268fd2870d8708/arbos/parse_l2.go#L292>
 #[derive(PartialEq, Debug, Clone, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -60,6 +84,97 @@ impl SubmitRetryableTx {
         self.from
     }
 
+    /// Returns the L1 message index used as request id.
+    pub const fn request_id(&self) -> B256 {
+        self.request_id
+    }
+
+    /// Returns the L1 base fee recorded with the message.
+    pub const fn l1_base_fee(&self) -> U256 {
+        self.l1_base_fee
+    }
+
+    /// Returns the ETH deposited with the ticket.
+    pub const fn deposit_value(&self) -> U256 {
+        self.deposit_value
+    }
+
+    /// Returns the maximum fee per gas of the auto-redeem.
+    pub const fn gas_fee_cap(&self) -> U256 {
+        self.gas_fee_cap
+    }
+
+    /// Returns the gas limit of the auto-redeem, saturating at `u64::MAX`.
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit.saturating_to()
+    }
+
+    /// Returns the retryable's call target, or `Create` for a deployment.
+    pub const fn retry_to(&self) -> TxKind {
+        self.retry_to
+    }
+
+    /// Returns the value passed to the retryable's call.
+    pub const fn retry_value(&self) -> U256 {
+        self.retry_value
+    }
+
+    /// Returns the address receiving the call value if the ticket expires or is cancelled.
+    pub const fn beneficiary(&self) -> Address {
+        self.beneficiary
+    }
+
+    /// Returns the maximum submission fee.
+    pub const fn max_submission_fee(&self) -> U256 {
+        self.max_submission_fee
+    }
+
+    /// Returns the address receiving unused fees.
+    pub const fn fee_refund_address(&self) -> Address {
+        self.fee_refund_address
+    }
+
+    /// Returns the calldata of the retryable's call.
+    pub const fn retry_data(&self) -> &Bytes {
+        &self.retry_data
+    }
+
+    /// Returns a builder for a submit-retryable transaction.
+    pub fn builder() -> SubmitRetryableTxBuilder {
+        SubmitRetryableTxBuilder::default()
+    }
+
+    /// Decodes the ArbOS `submitRetryable` calldata returned by `Transaction::input`.
+    ///
+    /// The chain id and sender are not part of the calldata and must be supplied.
+    pub fn decode_calldata(
+        calldata: &[u8],
+        chain_id: U256,
+        from: Address,
+    ) -> Result<Self, alloy_core::sol_types::Error> {
+        let call = abi::ArbRetryableTx::submitRetryableCall::abi_decode(calldata)?;
+        let retry_to = if call.retryTo.is_zero() {
+            TxKind::Create
+        } else {
+            TxKind::Call(call.retryTo)
+        };
+        Ok(Self::new(
+            chain_id,
+            call.requestId,
+            from,
+            call.l1BaseFee,
+            call.deposit,
+            call.gasFeeCap,
+            U256::from(call.gasLimit),
+            retry_to,
+            call.callvalue,
+            call.beneficiary,
+            call.maxSubmissionFee,
+            call.feeRefundAddress,
+            call.retryData,
+        ))
+    }
+
     /// Constructs a new submit-retryable transaction body.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -215,35 +330,48 @@ impl SubmitRetryableTx {
             + self.retry_data.length()
     }
     /// Decodes a retryable transaction in the format used by the sequencer.
+    ///
+    /// Nitro reference: `arbos/parse_l2.go` -> `parseSubmitRetryableMessage`.
     pub fn decode_fields_sequencer(
         buf: &mut &[u8],
         chain_id: U256,
         request_id: B256,
         sender: Address,
         l1_base_fee: U256,
-    ) -> alloy_rlp::Result<Self> {
-        buf.advance(12);
-        let retry_to_decoded: Address = decode(buf)?;
-        let retry_to = if retry_to_decoded == Address::default() {
+    ) -> Result<Self, SequencerDecodeError> {
+        let retry_to = decode_address_word(buf)?;
+        let retry_to = if retry_to.is_zero() {
             TxKind::Create
         } else {
-            TxKind::Call(retry_to_decoded)
+            TxKind::Call(retry_to)
         };
+        let retry_value = decode(buf)?;
+        let deposit_value = decode(buf)?;
+        let max_submission_fee = decode(buf)?;
+        let fee_refund_address = decode_address_word(buf)?;
+        let beneficiary = decode_address_word(buf)?;
+        let gas_limit = decode_u64(buf, "gas_limit")?;
+        let gas_fee_cap = decode(buf)?;
+        let retry_data_size: U256 = decode(buf)?;
+        let retry_data_len = usize::try_from(retry_data_size)
+            .ok()
+            .filter(|&len| len <= MAX_L2_MESSAGE_SIZE && len <= buf.len())
+            .ok_or(SequencerDecodeError::InvalidRetryDataLength {
+                declared: retry_data_size,
+                remaining: buf.len(),
+            })?;
+        let retry_data = decode_bytes(buf, retry_data_len)?;
         let mut this = Self {
             retry_to,
-            retry_value: decode(buf)?,
-            deposit_value: decode(buf)?,
-            max_submission_fee: decode(buf).inspect(|_| {
-                buf.advance(12);
-            })?,
-            fee_refund_address: decode(buf).inspect(|_| {
-                buf.advance(12);
-            })?,
-            beneficiary: decode(buf)?,
-            gas_limit: decode(buf)?,
-            gas_fee_cap: decode(buf)?,
-            retry_data_size: decode(buf)?,
-            retry_data: decode_rest(buf),
+            retry_value,
+            deposit_value,
+            max_submission_fee,
+            fee_refund_address,
+            beneficiary,
+            gas_limit: U256::from(gas_limit),
+            gas_fee_cap,
+            retry_data_size,
+            retry_data,
             chain_id,
             request_id,
             from: sender,
@@ -285,6 +413,161 @@ impl SubmitRetryableTx {
     }
 }
 
+/// Error returned by [`SubmitRetryableTxBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitRetryableBuildError {
+    /// A required field was not set.
+    MissingField(&'static str),
+    /// The retry data exceeds Nitro's L2 message size limit.
+    RetryDataTooLarge {
+        /// Retry data length in bytes.
+        len: usize,
+    },
+}
+
+impl fmt::Display for SubmitRetryableBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing submit-retryable field `{field}`"),
+            Self::RetryDataTooLarge { len } => write!(
+                f,
+                "retry data of {len} bytes exceeds the {MAX_L2_MESSAGE_SIZE} byte limit"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SubmitRetryableBuildError {}
+
+/// Builder for [`SubmitRetryableTx`].
+///
+/// The chain id, request id, sender and call target are required; amounts and refund
+/// addresses default to zero.
+#[derive(Debug, Clone, Default)]
+pub struct SubmitRetryableTxBuilder {
+    chain_id: Option<U256>,
+    request_id: Option<B256>,
+    from: Option<Address>,
+    retry_to: Option<TxKind>,
+    l1_base_fee: U256,
+    deposit_value: U256,
+    gas_fee_cap: U256,
+    gas_limit: u64,
+    retry_value: U256,
+    beneficiary: Address,
+    max_submission_fee: U256,
+    fee_refund_address: Address,
+    retry_data: Bytes,
+}
+
+impl SubmitRetryableTxBuilder {
+    /// Sets the L2 chain id.
+    pub const fn with_chain_id(mut self, chain_id: U256) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the request id, the L1 message index as a 32-byte word.
+    pub const fn with_request_id(mut self, request_id: B256) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
+
+    /// Sets the (aliased) L1 sender.
+    pub const fn with_from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Sets the call target, or `Create` for a deployment.
+    pub const fn with_retry_to(mut self, retry_to: TxKind) -> Self {
+        self.retry_to = Some(retry_to);
+        self
+    }
+
+    /// Sets the L1 base fee recorded with the message.
+    pub const fn with_l1_base_fee(mut self, l1_base_fee: U256) -> Self {
+        self.l1_base_fee = l1_base_fee;
+        self
+    }
+
+    /// Sets the ETH deposited with the ticket.
+    pub const fn with_deposit_value(mut self, deposit_value: U256) -> Self {
+        self.deposit_value = deposit_value;
+        self
+    }
+
+    /// Sets the maximum fee per gas of the auto-redeem.
+    pub const fn with_gas_fee_cap(mut self, gas_fee_cap: U256) -> Self {
+        self.gas_fee_cap = gas_fee_cap;
+        self
+    }
+
+    /// Sets the gas limit of the auto-redeem.
+    pub const fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the value passed to the call.
+    pub const fn with_retry_value(mut self, retry_value: U256) -> Self {
+        self.retry_value = retry_value;
+        self
+    }
+
+    /// Sets the address receiving the call value if the ticket expires or is cancelled.
+    pub const fn with_beneficiary(mut self, beneficiary: Address) -> Self {
+        self.beneficiary = beneficiary;
+        self
+    }
+
+    /// Sets the maximum submission fee.
+    pub const fn with_max_submission_fee(mut self, max_submission_fee: U256) -> Self {
+        self.max_submission_fee = max_submission_fee;
+        self
+    }
+
+    /// Sets the address receiving unused fees.
+    pub const fn with_fee_refund_address(mut self, fee_refund_address: Address) -> Self {
+        self.fee_refund_address = fee_refund_address;
+        self
+    }
+
+    /// Sets the calldata of the call.
+    pub fn with_retry_data(mut self, retry_data: impl Into<Bytes>) -> Self {
+        self.retry_data = retry_data.into();
+        self
+    }
+
+    /// Validates the fields and builds the transaction.
+    pub fn build(self) -> Result<SubmitRetryableTx, SubmitRetryableBuildError> {
+        if self.retry_data.len() > MAX_L2_MESSAGE_SIZE {
+            return Err(SubmitRetryableBuildError::RetryDataTooLarge {
+                len: self.retry_data.len(),
+            });
+        }
+        Ok(SubmitRetryableTx::new(
+            self.chain_id
+                .ok_or(SubmitRetryableBuildError::MissingField("chain_id"))?,
+            self.request_id
+                .ok_or(SubmitRetryableBuildError::MissingField("request_id"))?,
+            self.from
+                .ok_or(SubmitRetryableBuildError::MissingField("from"))?,
+            self.l1_base_fee,
+            self.deposit_value,
+            self.gas_fee_cap,
+            U256::from(self.gas_limit),
+            self.retry_to
+                .ok_or(SubmitRetryableBuildError::MissingField("retry_to"))?,
+            self.retry_value,
+            self.beneficiary,
+            self.max_submission_fee,
+            self.fee_refund_address,
+            self.retry_data,
+        ))
+    }
+}
+
 impl Decodable for SubmitRetryableTx {
     fn decode(data: &mut &[u8]) -> alloy_rlp::Result<Self> {
         Self::rlp_decode(data)
@@ -438,6 +721,107 @@ mod tests {
         )
     }
 
+    #[test]
+    fn decode_submit_retryable_rejects_truncated_input() {
+        let mut encoded = vec![0u8; 9 * 32];
+        encoded[6 * 32 + 31] = 1;
+        encoded[8 * 32 + 31] = 4;
+        for len in 0..encoded.len() {
+            assert!(
+                SubmitRetryableTx::decode_fields_sequencer(
+                    &mut &encoded[..len],
+                    U256::from(42161),
+                    B256::ZERO,
+                    Address::ZERO,
+                    U256::ZERO,
+                )
+                .is_err(),
+                "prefix of {len} bytes should not decode"
+            );
+        }
+        assert_eq!(
+            SubmitRetryableTx::decode_fields_sequencer(
+                &mut &encoded[..],
+                U256::from(42161),
+                B256::ZERO,
+                Address::ZERO,
+                U256::ZERO,
+            ),
+            Err(SequencerDecodeError::InvalidRetryDataLength {
+                declared: U256::from(4),
+                remaining: 0,
+            })
+        );
+
+        encoded.extend_from_slice(&[0xaa; 4]);
+        let tx = SubmitRetryableTx::decode_fields_sequencer(
+            &mut &encoded[..],
+            U256::from(42161),
+            B256::ZERO,
+            Address::ZERO,
+            U256::ZERO,
+        )
+        .unwrap();
+        assert_eq!(tx.retry_data, Bytes::from_static(&[0xaa; 4]));
+    }
+
+    #[test]
+    fn builder_round_trips_through_calldata() {
+        let tx = SubmitRetryableTx::builder()
+            .with_chain_id(U256::from(42161))
+            .with_request_id(B256::with_last_byte(9))
+            .with_from(address!("0x1111111111111111111111111111111111111111"))
+            .with_retry_to(TxKind::Call(address!(
+                "0x2222222222222222222222222222222222222222"
+            )))
+            .with_l1_base_fee(U256::from(30))
+            .with_deposit_value(U256::from(1_000_000))
+            .with_gas_fee_cap(U256::from(100))
+            .with_gas_limit(50_000)
+            .with_retry_value(U256::from(7))
+            .with_beneficiary(address!("0x3333333333333333333333333333333333333333"))
+            .with_max_submission_fee(U256::from(40_000))
+            .with_fee_refund_address(address!("0x4444444444444444444444444444444444444444"))
+            .with_retry_data([0xde, 0xad, 0xbe, 0xef])
+            .build()
+            .unwrap();
+        assert_eq!(tx.gas_limit(), 50_000);
+        assert_eq!(
+            tx.retry_data(),
+            &Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef])
+        );
+
+        let decoded =
+            SubmitRetryableTx::decode_calldata(tx.input(), U256::from(42161), tx.from()).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.tx_hash(), tx.tx_hash());
+
+        let create = SubmitRetryableTx::builder()
+            .with_chain_id(U256::from(42161))
+            .with_request_id(B256::ZERO)
+            .with_from(Address::ZERO)
+            .with_retry_to(TxKind::Create)
+            .build()
+            .unwrap();
+        let decoded =
+            SubmitRetryableTx::decode_calldata(create.input(), U256::from(42161), Address::ZERO)
+                .unwrap();
+        assert_eq!(decoded.retry_to(), TxKind::Create);
+        assert!(
+            SubmitRetryableTx::decode_calldata(&create.input()[..36], U256::ZERO, Address::ZERO)
+                .is_err()
+        );
+
+        assert_eq!(
+            SubmitRetryableTx::builder()
+                .with_chain_id(U256::from(1))
+                .with_request_id(B256::ZERO)
+                .with_retry_to(TxKind::Create)
+                .build(),
+            Err(SubmitRetryableBuildError::MissingField("from"))
+        );
+    }
+
     #[tokio::test]
     #[serial]
     async fn submit_retryable_produces_submit_retryable_tx_on_l2()
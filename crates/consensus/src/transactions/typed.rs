@@ -462,6 +462,7 @@ impl From<ArbTxEnvelope> for ArbitrumTypedTransaction {
             ArbTxEnvelope::Contract(tx) => Self::Contract(tx.clone_inner()),
             ArbTxEnvelope::Retry(tx) => Self::Retry(tx.clone_inner()),
             ArbTxEnvelope::Internal(tx) => Self::Internal(tx.clone_inner()),
+            ArbTxEnvelope::ArbitrumLegacy(tx) => Self::Legacy(tx.legacy_tx()),
         }
     }
 }
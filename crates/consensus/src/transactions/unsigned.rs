@@ -240,7 +240,7 @@ impl Sealable for TxUnsigned {
 mod tests {
     use alloy_eips::Typed2718;
     use alloy_network_primitives::{ReceiptResponse, TransactionResponse};
-    use alloy_primitives::{Address, B256, U256, address, uint};
+    use alloy_primitives::{B256, U256, address, uint};
     use alloy_provider::Provider;
     use alloy_rpc_types_eth::TransactionTrait;
     use serial_test::serial;
@@ -248,16 +248,6 @@ mod tests {
 
     use super::TxUnsigned;
 
-    const ADDRESS_ALIAS_OFFSET: U256 = uint!(0x1111000000000000000000000000000000001111_U256);
-
-    fn l1_to_l2_alias(addr: Address) -> Address {
-        let mut buf = [0u8; 32];
-        buf[12..].copy_from_slice(addr.as_slice());
-        let aliased = U256::from_be_bytes(buf).wrapping_add(ADDRESS_ALIAS_OFFSET);
-        let out = aliased.to_be_bytes::<32>();
-        Address::from_slice(&out[12..])
-    }
-
     #[test]
     fn deserialize_rpc_shape_supports_gas_price_alias() {
         let raw = r#"{
@@ -296,7 +286,7 @@ mod tests {
         println!("[unsigned] scanning L2 from block {since}");
 
         let l1_sender = dev_address();
-        let l2_sender = l1_to_l2_alias(l1_sender);
+        let l2_sender = crate::apply_l1_to_l2_alias(l1_sender);
         let l2_nonce = ctx
             .arbitrum_provider
             .get_transaction_count(l2_sender)
@@ -1,21 +1,135 @@
-use alloy_primitives::{Bytes, FixedBytes};
+use alloy_primitives::{Address, Bytes, FixedBytes, U256};
 use bytes::Buf;
+use core::fmt;
 
-/// Decodes `N` raw bytes from the current buffer and converts them into `F`.
-pub fn decode<const N: usize, F: From<FixedBytes<N>>>(buf: &mut &[u8]) -> alloy_rlp::Result<F> {
-    //read the length of T from the cursor
-    if buf.len() < N {
-        return Err(alloy_rlp::Error::InputTooShort);
+/// Error while decoding packed sequencer payload fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SequencerDecodeError {
+    /// The payload ended before a fixed-size field could be read.
+    InputTooShort {
+        /// Number of bytes the field needs.
+        needed: usize,
+        /// Number of bytes left in the payload.
+        remaining: usize,
+    },
+    /// A 256-bit field does not fit the integer type it is stored in.
+    Overflow {
+        /// Name of the offending field.
+        field: &'static str,
+    },
+    /// Batch data stats report more non-zero bytes than total bytes.
+    InvalidBatchDataStats {
+        /// Reported batch data length.
+        length: u64,
+        /// Reported number of non-zero bytes.
+        non_zeros: u64,
+    },
+    /// The legacy batch gas cost disagrees with the one computed from batch data stats.
+    LegacyGasMismatch {
+        /// Gas cost carried by the message.
+        reported: u64,
+        /// Gas cost computed from the batch data stats.
+        computed: u64,
+    },
+    /// A batch posting report carried neither a legacy gas cost nor batch data stats.
+    MissingBatchGasCost,
+    /// Batch data stats are required for ArbOS 50 and later.
+    MissingBatchDataStats,
+    /// The declared retryable data length exceeds the payload or Nitro's size limit.
+    InvalidRetryDataLength {
+        /// Declared data length.
+        declared: U256,
+        /// Number of bytes left in the payload.
+        remaining: usize,
+    },
+}
+
+impl fmt::Display for SequencerDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InputTooShort { needed, remaining } => {
+                write!(
+                    f,
+                    "sequencer payload too short: need {needed} bytes, {remaining} remaining"
+                )
+            }
+            Self::Overflow { field } => write!(f, "sequencer payload field `{field}` overflows"),
+            Self::InvalidBatchDataStats { length, non_zeros } => {
+                write!(
+                    f,
+                    "batch data stats report {non_zeros} non-zero bytes out of {length}"
+                )
+            }
+            Self::LegacyGasMismatch { reported, computed } => {
+                write!(
+                    f,
+                    "legacy batch gas cost {reported} does not match computed cost {computed}"
+                )
+            }
+            Self::MissingBatchGasCost => {
+                write!(
+                    f,
+                    "batch posting report has no legacy gas cost or batch data stats"
+                )
+            }
+            Self::MissingBatchDataStats => {
+                write!(f, "batch data stats are required for ArbOS 50 and later")
+            }
+            Self::InvalidRetryDataLength {
+                declared,
+                remaining,
+            } => {
+                write!(
+                    f,
+                    "retryable data length {declared} is invalid with {remaining} bytes remaining"
+                )
+            }
+        }
     }
-    let data: FixedBytes<N> = FixedBytes::from(
-        &buf[..N]
-            .try_into()
-            .map_err(|_| alloy_rlp::Error::InputTooShort)?,
-    );
-    buf.advance(N);
+}
+
+impl core::error::Error for SequencerDecodeError {}
+
+/// Decodes `N` raw bytes from the current buffer and converts them into `F`.
+pub fn decode<const N: usize, F: From<FixedBytes<N>>>(
+    buf: &mut &[u8],
+) -> Result<F, SequencerDecodeError> {
+    let Some((data, rest)) = buf.split_first_chunk::<N>() else {
+        return Err(SequencerDecodeError::InputTooShort {
+            needed: N,
+            remaining: buf.len(),
+        });
+    };
+    let data = FixedBytes::from(*data);
+    *buf = rest;
     Ok(F::from(data))
 }
 
+/// Decodes a 32-byte big-endian word that must fit in a `u64`.
+pub fn decode_u64(buf: &mut &[u8], field: &'static str) -> Result<u64, SequencerDecodeError> {
+    let value: U256 = decode(buf)?;
+    u64::try_from(value).map_err(|_| SequencerDecodeError::Overflow { field })
+}
+
+/// Decodes an address left-padded to a 32-byte word, ignoring the padding bytes.
+pub fn decode_address_word(buf: &mut &[u8]) -> Result<Address, SequencerDecodeError> {
+    let word: FixedBytes<32> = decode(buf)?;
+    Ok(Address::from_word(word))
+}
+
+/// Consumes exactly `len` bytes and returns them as [`Bytes`].
+pub fn decode_bytes(buf: &mut &[u8], len: usize) -> Result<Bytes, SequencerDecodeError> {
+    if buf.len() < len {
+        return Err(SequencerDecodeError::InputTooShort {
+            needed: len,
+            remaining: buf.len(),
+        });
+    }
+    let data = Bytes::copy_from_slice(&buf[..len]);
+    buf.advance(len);
+    Ok(data)
+}
+
 /// Consumes and returns the remaining buffer contents as [`Bytes`].
 pub fn decode_rest(buf: &mut &[u8]) -> Bytes {
     // read the rest of the buffer as Bytes
@@ -23,3 +137,45 @@ pub fn decode_rest(buf: &mut &[u8]) -> Bytes {
     *buf = &[];
     data
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let mut buf = &[0u8; 31][..];
+        assert_eq!(
+            decode::<32, U256>(&mut buf),
+            Err(SequencerDecodeError::InputTooShort {
+                needed: 32,
+                remaining: 31
+            })
+        );
+        assert_eq!(buf.len(), 31);
+        assert_eq!(
+            decode_bytes(&mut buf, 40),
+            Err(SequencerDecodeError::InputTooShort {
+                needed: 40,
+                remaining: 31
+            })
+        );
+    }
+
+    #[test]
+    fn word_helpers_check_ranges() {
+        let mut word = [0xffu8; 32];
+        assert_eq!(
+            decode_u64(&mut &word[..], "gas"),
+            Err(SequencerDecodeError::Overflow { field: "gas" })
+        );
+
+        word[12..]
+            .copy_from_slice(address!("0x1111111111111111111111111111111111111111").as_slice());
+        assert_eq!(
+            decode_address_word(&mut &word[..]).unwrap(),
+            address!("0x1111111111111111111111111111111111111111")
+        );
+    }
+}
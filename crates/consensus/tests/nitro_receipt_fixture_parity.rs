@@ -100,7 +100,8 @@ const fn gas_used_for_l1<T>(receipt: &ArbReceiptEnvelope<T>) -> u64 {
         | ArbReceiptEnvelope::Contract(r)
         | ArbReceiptEnvelope::Retry(r)
         | ArbReceiptEnvelope::SubmitRetryable(r)
-        | ArbReceiptEnvelope::Internal(r) => r.receipt.gas_used_for_l1,
+        | ArbReceiptEnvelope::Internal(r)
+        | ArbReceiptEnvelope::ArbitrumLegacy(r) => r.receipt.gas_used_for_l1,
     }
 }
 
@@ -41,6 +41,25 @@ alloy_core::sol! {
 
         /// Emitted when a retryable ticket's lifetime is extended.
         event LifetimeExtended(bytes32 indexed ticketId, uint256 newTimeout);
+
+        /// Emitted when a retry of a ticket is scheduled, by the submission's auto-redeem
+        /// or by `redeem`. Links the ticket to the hash of its retry transaction.
+        event RedeemScheduled(
+            bytes32 indexed ticketId,
+            bytes32 indexed retryTxHash,
+            uint64 indexed sequenceNum,
+            uint64 donatedGas,
+            address gasDonor,
+            uint256 maxRefund,
+            uint256 submissionFeeRefund
+        );
+
+        /// Reverted with when no live ticket has the given id.
+        error NoTicketWithID();
+
+        /// Reverted with when a method is called from a context it does not support,
+        /// such as redeeming a ticket from its own retry.
+        error NotCallable();
     }
 }
 
@@ -8,6 +8,8 @@ mod arb_owner;
 mod arb_owner_public;
 mod arb_retryable_tx;
 mod arb_statistics;
+// The generated `L2ToL1Tx` event constructor takes nine arguments.
+#[allow(clippy::too_many_arguments)]
 mod arb_sys;
 mod arb_wasm;
 mod arb_wasm_cache;
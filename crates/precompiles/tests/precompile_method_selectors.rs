@@ -966,6 +966,25 @@ fn arb_retryable_tx_method_selectors() {
     );
 }
 
+#[test]
+fn arb_retryable_tx_event_and_error_signatures() {
+    use alloy_core::sol_types::{SolError, SolEvent};
+
+    assert_eq!(
+        ArbRetryableTx::RedeemScheduled::SIGNATURE,
+        "RedeemScheduled(bytes32,bytes32,uint64,uint64,address,uint256,uint256)"
+    );
+    assert_eq!(
+        ArbRetryableTx::LifetimeExtended::SIGNATURE,
+        "LifetimeExtended(bytes32,uint256)"
+    );
+    assert_eq!(
+        ArbRetryableTx::NoTicketWithID::SIGNATURE,
+        "NoTicketWithID()"
+    );
+    assert_eq!(ArbRetryableTx::NotCallable::SIGNATURE, "NotCallable()");
+}
+
 #[test]
 fn arb_statistics_method_selectors() {
     assert_method!(
@@ -0,0 +1,448 @@
+use alloc::vec::Vec;
+use core::{fmt, time::Duration};
+
+use alloy_core::{sol, sol_types::SolCall};
+use alloy_network::{Ethereum, TransactionBuilder};
+use alloy_primitives::{Address, B256, U256};
+use alloy_provider::{PendingTransactionBuilder, PendingTransactionError, Provider};
+use alloy_rpc_types_eth::{TransactionReceipt, TransactionRequest};
+use alloy_transport::TransportError;
+use arb_alloy_consensus::{
+    ArbTxEnvelope, ParseL2Error, inbox::delayed::DelayedInboxError, transactions::TxDeposit,
+};
+use arb_alloy_network::Arbitrum;
+use arb_alloy_rpc_types::ArbTransactionReceipt;
+use arb_sequencer_network::sequencer::feed::MessageType;
+
+use crate::inbox::delivered_transactions;
+
+// Nitro reference
+// - contracts/src/bridge/Inbox.sol: depositEth() delivers an `L1MessageType_ethDeposit`
+//   message with data abi.encodePacked(dest, msg.value); dest is the sender, aliased when the
+//   sender is a contract
+// - contracts/src/bridge/ERC20Inbox.sol: depositERC20(amount) pulls the native token into the
+//   bridge and delivers the same message kind, with the amount scaled to 18 decimals
+// - arbos/parse_l2.go: parseEthDepositMessage(); the `TxDeposit` request id is the message
+//   index
+sol! {
+    interface IInbox {
+        function depositEth() external payable returns (uint256);
+    }
+
+    interface IERC20Inbox {
+        function depositERC20(uint256 amount) external returns (uint256);
+    }
+}
+
+/// Time [`Depositor`] waits for a deposit to execute on L2 by default.
+const DEFAULT_DEPOSIT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Error while depositing to L2.
+#[derive(Debug)]
+pub enum DepositError {
+    /// An RPC request failed.
+    Transport(TransportError),
+    /// Waiting for a transaction failed or timed out.
+    Pending(PendingTransactionError),
+    /// The L1 deposit transaction reverted.
+    L1Reverted {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+    },
+    /// The L1 receipt has no block number.
+    MissingBlockNumber {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+    },
+    /// The L1 receipt delivered no deposit message.
+    NoDeposit {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+    },
+    /// A delayed inbox log did not match its message data.
+    Delayed(DelayedInboxError),
+    /// A deposit message did not decode into a `TxDeposit`.
+    Parse(ParseL2Error),
+}
+
+impl fmt::Display for DepositError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "RPC request failed: {err}"),
+            Self::Pending(err) => write!(f, "waiting for transaction failed: {err}"),
+            Self::L1Reverted { tx_hash } => write!(f, "L1 deposit {tx_hash} reverted"),
+            Self::MissingBlockNumber { tx_hash } => {
+                write!(f, "L1 receipt {tx_hash} has no block number")
+            }
+            Self::NoDeposit { tx_hash } => {
+                write!(f, "L1 transaction {tx_hash} delivered no deposit")
+            }
+            Self::Delayed(err) => write!(f, "invalid delayed inbox message: {err}"),
+            Self::Parse(err) => write!(f, "invalid deposit message: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for DepositError {}
+
+impl From<TransportError> for DepositError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl From<PendingTransactionError> for DepositError {
+    fn from(err: PendingTransactionError) -> Self {
+        Self::Pending(err)
+    }
+}
+
+impl From<DelayedInboxError> for DepositError {
+    fn from(err: DelayedInboxError) -> Self {
+        Self::Delayed(err)
+    }
+}
+
+impl From<ParseL2Error> for DepositError {
+    fn from(err: ParseL2Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Deposits the L2 native token through the delayed inbox and waits for the `TxDeposit`.
+#[derive(Debug, Clone)]
+pub struct Depositor<L1, L2> {
+    l1: L1,
+    l2: L2,
+    bridge: Address,
+    inbox: Address,
+    timeout: Duration,
+}
+
+impl<L1, L2> Depositor<L1, L2>
+where
+    L1: Provider<Ethereum>,
+    L2: Provider<Arbitrum>,
+{
+    /// Creates a depositor sending to `inbox` on `l1` and waiting on `l2`.
+    ///
+    /// `inbox` is the `ERC20Inbox` on chains with a custom gas token, and `bridge` the bridge it
+    /// delivers messages through.
+    pub const fn new(l1: L1, l2: L2, bridge: Address, inbox: Address) -> Self {
+        Self {
+            l1,
+            l2,
+            bridge,
+            inbox,
+            timeout: DEFAULT_DEPOSIT_TIMEOUT,
+        }
+    }
+
+    /// Sets how long to wait for a deposit to execute on L2.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the `Inbox.depositEth` request crediting `value` to the sender on L2.
+    pub fn deposit_eth_request(&self, value: U256) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_to(self.inbox)
+            .with_value(value)
+            .with_input(IInbox::depositEthCall {}.abi_encode())
+    }
+
+    /// Returns the `ERC20Inbox.depositERC20` request crediting `amount` of the native token to
+    /// the sender on L2.
+    ///
+    /// The bridge must be approved to spend `amount` of the token.
+    pub fn deposit_erc20_request(&self, amount: U256) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_to(self.inbox)
+            .with_input(IERC20Inbox::depositERC20Call { amount }.abi_encode())
+    }
+
+    /// Deposits `value` ETH and returns the L2 receipt of the `TxDeposit`.
+    pub async fn deposit_eth(&self, value: U256) -> Result<ArbTransactionReceipt, DepositError> {
+        self.send(self.deposit_eth_request(value)).await
+    }
+
+    /// Deposits `amount` of the custom gas token and returns the L2 receipt of the `TxDeposit`.
+    pub async fn deposit_erc20(&self, amount: U256) -> Result<ArbTransactionReceipt, DepositError> {
+        self.send(self.deposit_erc20_request(amount)).await
+    }
+
+    /// Waits for the deposit delivered by an L1 transaction and returns its L2 receipt.
+    pub async fn wait_for_deposit(
+        &self,
+        l1_receipt: &TransactionReceipt,
+    ) -> Result<ArbTransactionReceipt, DepositError> {
+        let chain_id = self.l2.get_chain_id().await?;
+        let deposit = deposits(l1_receipt, self.bridge, self.inbox, chain_id)?
+            .into_iter()
+            .next()
+            .ok_or(DepositError::NoDeposit {
+                tx_hash: l1_receipt.transaction_hash,
+            })?;
+        let receipt = PendingTransactionBuilder::new(self.l2.root().clone(), deposit.tx_hash())
+            .with_timeout(Some(self.timeout))
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    async fn send(&self, tx: TransactionRequest) -> Result<ArbTransactionReceipt, DepositError> {
+        let l1_receipt = self.l1.send_transaction(tx).await?.get_receipt().await?;
+        if !l1_receipt.status() {
+            return Err(DepositError::L1Reverted {
+                tx_hash: l1_receipt.transaction_hash,
+            });
+        }
+        self.wait_for_deposit(&l1_receipt).await
+    }
+}
+
+/// Returns the `TxDeposit`s for the deposit messages `inbox` delivered through `bridge` in an
+/// L1 receipt.
+pub fn deposits(
+    l1_receipt: &TransactionReceipt,
+    bridge: Address,
+    inbox: Address,
+    chain_id: u64,
+) -> Result<Vec<TxDeposit>, DepositError> {
+    let block_number = l1_receipt
+        .block_number
+        .ok_or(DepositError::MissingBlockNumber {
+            tx_hash: l1_receipt.transaction_hash,
+        })?;
+    Ok(delivered_transactions::<DepositError>(
+        l1_receipt,
+        block_number,
+        bridge,
+        inbox,
+        chain_id,
+        MessageType::EthDeposit,
+    )?
+    .into_iter()
+    .filter_map(|tx| match tx {
+        ArbTxEnvelope::Deposit(deposit) => Some(deposit.into_inner()),
+        _ => None,
+    })
+    .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy_primitives::{Bloom, Bytes, U64, address, keccak256};
+    use alloy_provider::{ProviderBuilder, WatchTxError};
+    use alloy_rpc_types_eth::Log;
+    use alloy_transport::mock::Asserter;
+    use arb_alloy_consensus::{
+        ArbReceipt, ArbReceiptEnvelope, apply_l1_to_l2_alias,
+        inbox::{
+            delayed::{InboxMessageDelivered, MessageDelivered},
+            predict::InboxSubmission,
+        },
+    };
+
+    const BRIDGE: Address = address!("0x8315177aB297bA92A06054cE80a67Ed4DBd7ed3a");
+    const INBOX: Address = address!("0x4Dbd4fc535Ac27206064B68FfCf827b0A60BAB3f");
+    const SENDER: Address = address!("0x3333333333333333333333333333333333333333");
+
+    fn l1_receipt(index: u64, kind: MessageType, data: &[u8]) -> TransactionReceipt {
+        let bridge = MessageDelivered {
+            messageIndex: U256::from(index),
+            beforeInboxAcc: B256::ZERO,
+            inbox: INBOX,
+            kind: kind.to_u8(),
+            sender: apply_l1_to_l2_alias(SENDER),
+            messageDataHash: keccak256(data),
+            baseFeeL1: U256::from(10),
+            timestamp: 1_700_000_000,
+        };
+        let inbox = InboxMessageDelivered {
+            messageNum: U256::from(index),
+            data: Bytes::copy_from_slice(data),
+        };
+        let logs = vec![
+            Log {
+                inner: alloy_primitives::Log::new_from_event_unchecked(BRIDGE, bridge)
+                    .reserialize(),
+                ..Default::default()
+            },
+            Log {
+                inner: alloy_primitives::Log::new_from_event_unchecked(INBOX, inbox).reserialize(),
+                ..Default::default()
+            },
+        ];
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: Eip658Value::Eip658(true),
+                    cumulative_gas_used: 0,
+                    logs,
+                },
+                logs_bloom: Bloom::ZERO,
+            }),
+            transaction_hash: B256::repeat_byte(1),
+            transaction_index: Some(0),
+            block_hash: None,
+            block_number: Some(19_000_000),
+            gas_used: 0,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: SENDER,
+            to: Some(INBOX),
+            contract_address: None,
+        }
+    }
+
+    fn deposit_data(value: U256) -> Vec<u8> {
+        let mut data = SENDER.to_vec();
+        data.extend_from_slice(&value.to_be_bytes::<32>());
+        data
+    }
+
+    fn l2_receipt_json(tx_hash: B256) -> serde_json::Value {
+        serde_json::to_value(ArbTransactionReceipt {
+            inner: TransactionReceipt {
+                inner: ArbReceiptEnvelope::Deposit(ReceiptWithBloom {
+                    receipt: ArbReceipt::new(Receipt {
+                        status: Eip658Value::Eip658(true),
+                        cumulative_gas_used: 0,
+                        logs: Vec::new(),
+                    }),
+                    logs_bloom: Bloom::ZERO,
+                }),
+                transaction_hash: tx_hash,
+                transaction_index: Some(0),
+                block_hash: Some(B256::ZERO),
+                block_number: Some(10),
+                gas_used: 0,
+                effective_gas_price: 0,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                from: SENDER,
+                to: Some(SENDER),
+                contract_address: None,
+            },
+            gas_used_for_l1: 0,
+            l1_block_number: None,
+            timeboosted: None,
+        })
+        .unwrap()
+    }
+
+    fn depositor(l2: Asserter) -> Depositor<impl Provider<Ethereum>, impl Provider<Arbitrum>> {
+        Depositor::new(
+            ProviderBuilder::default().connect_mocked_client(Asserter::new()),
+            ProviderBuilder::<_, _, Arbitrum>::default().connect_mocked_client(l2),
+            BRIDGE,
+            INBOX,
+        )
+    }
+
+    #[test]
+    fn deposit_requests_and_l2_hash() {
+        let depositor = depositor(Asserter::new());
+        let value = U256::from(1_000_000);
+
+        let request = depositor.deposit_eth_request(value);
+        assert_eq!(request.to, Some(INBOX.into()));
+        assert_eq!(request.value, Some(value));
+        assert_eq!(
+            request.input.input().unwrap()[..],
+            IInbox::depositEthCall::SELECTOR
+        );
+        let request = depositor.deposit_erc20_request(value);
+        assert_eq!(request.value, None);
+        assert_eq!(
+            IERC20Inbox::depositERC20Call::abi_decode(request.input.input().unwrap())
+                .unwrap()
+                .amount,
+            value
+        );
+
+        let data = deposit_data(value);
+        let receipt = l1_receipt(42, MessageType::EthDeposit, &data);
+        let predicted = InboxSubmission::new(412346, 42, apply_l1_to_l2_alias(SENDER), U256::ZERO)
+            .deposit(SENDER, value);
+        let found = deposits(&receipt, BRIDGE, INBOX, 412346).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].tx_hash(), *predicted.tx_hash());
+
+        // Messages of another chain's inbox or bridge are not ours.
+        let other = address!("0x5555555555555555555555555555555555555555");
+        assert!(
+            deposits(&receipt, BRIDGE, other, 412346)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(deposits(&receipt, other, INBOX, 412346).unwrap().is_empty());
+
+        let mut pending = receipt.clone();
+        pending.block_number = None;
+        assert!(matches!(
+            deposits(&pending, BRIDGE, INBOX, 412346),
+            Err(DepositError::MissingBlockNumber { tx_hash }) if tx_hash == receipt.transaction_hash
+        ));
+
+        let receipt = l1_receipt(42, MessageType::SubmitRetryable, &data);
+        assert!(
+            deposits(&receipt, BRIDGE, INBOX, 412346)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_for_deposit_returns_the_l2_receipt() {
+        let value = U256::from(1_000_000);
+        let l1 = l1_receipt(42, MessageType::EthDeposit, &deposit_data(value));
+        let tx_hash = deposits(&l1, BRIDGE, INBOX, 412346).unwrap()[0].tx_hash();
+
+        let asserter = Asserter::new();
+        asserter.push_success(&U64::from(412346));
+        // Fetched once when registering the watch and once more for the result.
+        asserter.push_success(&l2_receipt_json(tx_hash));
+        asserter.push_success(&l2_receipt_json(tx_hash));
+        let receipt = depositor(asserter).wait_for_deposit(&l1).await.unwrap();
+        assert_eq!(receipt.inner.transaction_hash, tx_hash);
+
+        let asserter = Asserter::new();
+        asserter.push_success(&U64::from(412346));
+        let l1 = l1_receipt(42, MessageType::SubmitRetryable, &deposit_data(value));
+        assert!(matches!(
+            depositor(asserter).wait_for_deposit(&l1).await,
+            Err(DepositError::NoDeposit { tx_hash }) if tx_hash == l1.transaction_hash
+        ));
+    }
+
+    #[tokio::test]
+    async fn wait_for_deposit_times_out() {
+        let l1 = l1_receipt(42, MessageType::EthDeposit, &deposit_data(U256::from(1)));
+        let asserter = Asserter::new();
+        asserter.push_success(&U64::from(412346));
+        // Not executed yet; the timeout fires before the next receipt poll. The block poller
+        // may take one of these responses too, which only fails its first poll.
+        for _ in 0..3 {
+            asserter.push_success(&Option::<ArbTransactionReceipt>::None);
+        }
+        let result = depositor(asserter)
+            .with_timeout(Duration::from_millis(20))
+            .wait_for_deposit(&l1)
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(DepositError::Pending(PendingTransactionError::TxWatcher(
+                    WatchTxError::Timeout
+                )))
+            ),
+            "{result:?}"
+        );
+    }
+}
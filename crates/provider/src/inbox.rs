@@ -0,0 +1,59 @@
+use alloc::vec::Vec;
+
+use alloy_primitives::Address;
+
+use alloy_rpc_types_eth::TransactionReceipt;
+use arb_alloy_consensus::{
+    ArbTxEnvelope, ParseL2Error,
+    inbox::delayed::{DelayedInboxError, DelayedMessageData, DeliveredMessage},
+    parse_l2_transactions,
+};
+use arb_sequencer_network::sequencer::feed::MessageType;
+
+// Nitro reference
+// - arbnode/delayed.go: Bridge `MessageDelivered` logs pair with Inbox `InboxMessageDelivered`
+//   data logs by message index
+
+/// Returns the L2 transactions of the `kind` delayed messages `inbox` delivered through `bridge`
+/// in an L1 receipt mined in `block_number`, in message order.
+///
+/// Logs of other contracts, e.g. the inbox of another chain called in the same transaction, are
+/// ignored.
+pub(crate) fn delivered_transactions<E>(
+    l1_receipt: &TransactionReceipt,
+    block_number: u64,
+    bridge: Address,
+    inbox: Address,
+    chain_id: u64,
+    kind: MessageType,
+) -> Result<Vec<ArbTxEnvelope>, E>
+where
+    E: From<DelayedInboxError> + From<ParseL2Error>,
+{
+    let logs = l1_receipt.inner.logs();
+    let data: Vec<_> = logs
+        .iter()
+        .filter(|log| log.address() == inbox)
+        .filter_map(|log| DelayedMessageData::from_log(&log.inner).ok())
+        .collect();
+
+    let mut transactions = Vec::new();
+    for log in logs.iter().filter(|log| log.address() == bridge) {
+        let Ok(delivered) = DeliveredMessage::from_log(&log.inner, block_number) else {
+            continue;
+        };
+        if delivered.inbox != inbox || delivered.kind != kind.to_u8() {
+            continue;
+        }
+        let Some(data) = data
+            .iter()
+            .find(|data| data.message_index == delivered.message_index)
+        else {
+            continue;
+        };
+        let msg = delivered.with_data(data)?;
+        // Only batch posting reports depend on the ArbOS version.
+        transactions.extend(parse_l2_transactions(&msg, chain_id, 0)?);
+    }
+    Ok(transactions)
+}
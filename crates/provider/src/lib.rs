@@ -9,10 +9,19 @@
 
 extern crate alloc;
 
+mod deposit;
 mod ext;
+mod inbox;
+mod retryable;
+mod withdrawal;
 
+pub use deposit::{DepositError, Depositor, deposits};
 pub use ext::arb::ArbProviderExt;
 pub use ext::arbdebug::ArbDebugProviderExt;
 pub use ext::arbtrace::ArbTraceProviderExt;
 pub use ext::auctioneer::AuctioneerProviderExt;
 pub use ext::timeboost::TimeboostProviderExt;
+pub use retryable::{RetryableError, RetryableStatus, RetryableTracker, retryable_submissions};
+pub use withdrawal::{
+    ConfirmedState, Withdrawal, WithdrawalError, WithdrawalStatus, WithdrawalTracker,
+};
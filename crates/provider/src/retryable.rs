@@ -0,0 +1,617 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use alloy_core::sol_types::{SolCall, SolEventInterface};
+use alloy_network::{Network, ReceiptResponse, TransactionBuilder};
+use alloy_primitives::{Address, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::{Filter, TransactionReceipt};
+use alloy_transport::TransportError;
+use arb_alloy_consensus::{
+    ArbTxEnvelope, ParseL2Error, inbox::delayed::DelayedInboxError,
+    transactions::submit_retryable::SubmitRetryableTx,
+};
+use arb_alloy_network::Arbitrum;
+use arb_alloy_precompiles::{
+    ArbRetryableTx::{self, ArbRetryableTxErrors, ArbRetryableTxEvents},
+    addresses::ARB_RETRYABLE_TX,
+};
+use arb_sequencer_network::sequencer::feed::MessageType;
+
+use crate::inbox::delivered_transactions;
+
+// Nitro reference
+// - arbos/parse_l2.go: parseSubmitRetryableMessage(); the ticket id is the submit tx hash
+// - arbos/tx_processor.go: a successful SubmitRetryableTx emits `TicketCreated` and a
+//   `RedeemScheduled` for the auto-redeem `TxRetry`
+// - precompiles/ArbRetryableTx.go:
+//   - Redeem() emits `RedeemScheduled` with the retry tx hash and the ticket's next sequence num
+//   - Keepalive() emits `LifetimeExtended`, Cancel() emits `Canceled`
+//   - GetTimeout() reverts with `NoTicketWithID` once the ticket was redeemed, cancelled or
+//     expired
+
+/// Blocks [`RetryableTracker`] covers with one `eth_getLogs` request by default.
+const DEFAULT_LOG_BLOCK_RANGE: u64 = 100_000;
+
+/// Error while tracking a retryable ticket.
+#[derive(Debug)]
+pub enum RetryableError {
+    /// An RPC request failed.
+    Transport(TransportError),
+    /// A contract call returned malformed data.
+    Abi(alloy_core::sol_types::Error),
+    /// The L1 receipt has no block number.
+    MissingBlockNumber {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+    },
+    /// A delayed inbox log did not match its message data.
+    Delayed(DelayedInboxError),
+    /// A retryable message did not decode into a `SubmitRetryableTx`.
+    Parse(ParseL2Error),
+}
+
+impl fmt::Display for RetryableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "RPC request failed: {err}"),
+            Self::Abi(err) => write!(f, "invalid contract return data: {err}"),
+            Self::MissingBlockNumber { tx_hash } => {
+                write!(f, "L1 receipt {tx_hash} has no block number")
+            }
+            Self::Delayed(err) => write!(f, "invalid delayed inbox message: {err}"),
+            Self::Parse(err) => write!(f, "invalid retryable message: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for RetryableError {}
+
+impl From<TransportError> for RetryableError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl From<alloy_core::sol_types::Error> for RetryableError {
+    fn from(err: alloy_core::sol_types::Error) -> Self {
+        Self::Abi(err)
+    }
+}
+
+impl From<DelayedInboxError> for RetryableError {
+    fn from(err: DelayedInboxError) -> Self {
+        Self::Delayed(err)
+    }
+}
+
+impl From<ParseL2Error> for RetryableError {
+    fn from(err: ParseL2Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Stage of a retryable ticket on L2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryableStatus {
+    /// The `SubmitRetryableTx` is not on L2 yet.
+    NotCreated,
+    /// The `SubmitRetryableTx` failed and no ticket was created.
+    CreationFailed,
+    /// The ticket exists and can be redeemed until `timeout`.
+    Redeemable {
+        /// Timestamp the ticket expires at, including lifetime extensions.
+        timeout: u64,
+        /// Hashes of the `TxRetry` attempts that failed, auto-redeem first.
+        failed_retries: Vec<B256>,
+        /// Hashes of the `keepalive` transactions that extended the ticket lifetime.
+        keepalives: Vec<B256>,
+    },
+    /// A `TxRetry` succeeded.
+    Redeemed {
+        /// Hash of the successful `TxRetry`.
+        retry_tx: B256,
+        /// Whether the successful `TxRetry` was the auto-redeem scheduled at submission,
+        /// rather than a manual `redeem`.
+        auto_redeem: bool,
+    },
+    /// The ticket was cancelled by its beneficiary.
+    Canceled {
+        /// Hash of the transaction that cancelled the ticket.
+        cancel_tx: B256,
+    },
+    /// The ticket expired without a successful redeem.
+    Expired {
+        /// Hashes of the `TxRetry` attempts that failed, auto-redeem first.
+        failed_retries: Vec<B256>,
+    },
+}
+
+/// Tracks retryable tickets from their L1 submission through redemption on L2.
+#[derive(Debug, Clone)]
+pub struct RetryableTracker<P> {
+    l2: P,
+    log_block_range: u64,
+}
+
+impl<P> RetryableTracker<P>
+where
+    P: Provider<Arbitrum>,
+{
+    /// Creates a tracker reading tickets from `l2`.
+    pub const fn new(l2: P) -> Self {
+        Self {
+            l2,
+            log_block_range: DEFAULT_LOG_BLOCK_RANGE,
+        }
+    }
+
+    /// Sets how many blocks one `eth_getLogs` request covers when reading ticket events.
+    pub const fn with_log_block_range(mut self, blocks: u64) -> Self {
+        self.log_block_range = if blocks == 0 { 1 } else { blocks };
+        self
+    }
+
+    /// Returns the `SubmitRetryableTx`s `inbox` created through `bridge` in an L1 transaction,
+    /// in message order.
+    ///
+    /// Their hashes are the ticket ids.
+    pub async fn tickets(
+        &self,
+        l1_receipt: &TransactionReceipt,
+        bridge: Address,
+        inbox: Address,
+    ) -> Result<Vec<SubmitRetryableTx>, RetryableError> {
+        let chain_id = self.l2.get_chain_id().await?;
+        retryable_submissions(l1_receipt, bridge, inbox, chain_id)
+    }
+
+    /// Returns the stage of the ticket `ticket_id`.
+    pub async fn status(&self, ticket_id: B256) -> Result<RetryableStatus, RetryableError> {
+        let Some(submit) = self.l2.get_transaction_receipt(ticket_id).await? else {
+            return Ok(RetryableStatus::NotCreated);
+        };
+        if !submit.status() {
+            return Ok(RetryableStatus::CreationFailed);
+        }
+
+        let mut retries = Vec::new();
+        let mut keepalives = Vec::new();
+        let mut cancel_tx = None;
+        for log in self
+            .ticket_logs(ticket_id, submit.block_number().unwrap_or_default())
+            .await?
+        {
+            match ArbRetryableTxEvents::decode_log(&log.inner).map(|log| log.data) {
+                Ok(ArbRetryableTxEvents::RedeemScheduled(event)) => {
+                    retries.push((event.sequenceNum, event.retryTxHash));
+                }
+                Ok(ArbRetryableTxEvents::LifetimeExtended(_)) => {
+                    keepalives.extend(log.transaction_hash);
+                }
+                Ok(ArbRetryableTxEvents::Canceled(_)) => cancel_tx = log.transaction_hash,
+                _ => {}
+            }
+        }
+        retries.sort_by_key(|(sequence_num, _)| *sequence_num);
+
+        let mut failed_retries = Vec::new();
+        for (sequence_num, retry_tx) in retries {
+            let succeeded = self
+                .l2
+                .get_transaction_receipt(retry_tx)
+                .await?
+                .is_some_and(|receipt| receipt.status());
+            if succeeded {
+                return Ok(RetryableStatus::Redeemed {
+                    retry_tx,
+                    auto_redeem: sequence_num == 0,
+                });
+            }
+            failed_retries.push(retry_tx);
+        }
+        if let Some(cancel_tx) = cancel_tx {
+            return Ok(RetryableStatus::Canceled { cancel_tx });
+        }
+
+        let call = ArbRetryableTx::getTimeoutCall {
+            ticketId: ticket_id,
+        };
+        let tx = <Arbitrum as Network>::TransactionRequest::default()
+            .with_to(ARB_RETRYABLE_TX)
+            .with_input(call.abi_encode());
+        match self.l2.call(tx).await {
+            Ok(ret) => {
+                let timeout = ArbRetryableTx::getTimeoutCall::abi_decode_returns(&ret)?;
+                Ok(RetryableStatus::Redeemable {
+                    timeout: timeout.saturating_to(),
+                    failed_retries,
+                    keepalives,
+                })
+            }
+            Err(err) => match err
+                .as_error_resp()
+                .and_then(|resp| resp.as_decoded_interface_error::<ArbRetryableTxErrors>())
+            {
+                Some(ArbRetryableTxErrors::NoTicketWithID(_)) => {
+                    Ok(RetryableStatus::Expired { failed_retries })
+                }
+                _ => Err(err.into()),
+            },
+        }
+    }
+
+    /// Returns the `ArbRetryableTx` events of `ticket_id` from block `from` to the latest block,
+    /// at most `log_block_range` blocks per request.
+    async fn ticket_logs(
+        &self,
+        ticket_id: B256,
+        from: u64,
+    ) -> Result<Vec<alloy_rpc_types_eth::Log>, RetryableError> {
+        let latest = self.l2.get_block_number().await?;
+        let mut logs = Vec::new();
+        let mut start = from;
+        while start <= latest {
+            let end = start.saturating_add(self.log_block_range - 1).min(latest);
+            let filter = Filter::new()
+                .address(ARB_RETRYABLE_TX)
+                .topic1(ticket_id)
+                .from_block(start)
+                .to_block(end);
+            logs.extend(self.l2.get_logs(&filter).await?);
+            let Some(next) = end.checked_add(1) else {
+                break;
+            };
+            start = next;
+        }
+        Ok(logs)
+    }
+}
+
+/// Returns the `SubmitRetryableTx`s for the retryable messages `inbox` delivered through
+/// `bridge` in an L1 receipt.
+pub fn retryable_submissions(
+    l1_receipt: &TransactionReceipt,
+    bridge: Address,
+    inbox: Address,
+    chain_id: u64,
+) -> Result<Vec<SubmitRetryableTx>, RetryableError> {
+    let block_number = l1_receipt
+        .block_number
+        .ok_or(RetryableError::MissingBlockNumber {
+            tx_hash: l1_receipt.transaction_hash,
+        })?;
+    Ok(delivered_transactions::<RetryableError>(
+        l1_receipt,
+        block_number,
+        bridge,
+        inbox,
+        chain_id,
+        MessageType::SubmitRetryable,
+    )?
+    .into_iter()
+    .filter_map(|tx| match tx {
+        ArbTxEnvelope::SubmitRetryable(submit) => Some(submit.into_inner()),
+        _ => None,
+    })
+    .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy_core::sol_types::{SolError, SolEvent, SolValue};
+    use alloy_primitives::{Bloom, Bytes, TxKind, U64, U256, address, keccak256};
+    use alloy_provider::ProviderBuilder;
+    use alloy_rpc_types_eth::Log;
+    use alloy_transport::mock::Asserter;
+    use arb_alloy_consensus::{
+        ArbReceipt, ArbReceiptEnvelope,
+        inbox::{
+            delayed::{InboxMessageDelivered, MessageDelivered},
+            predict::{InboxSubmission, RetryableSubmission},
+        },
+    };
+    use arb_alloy_rpc_types::ArbTransactionReceipt;
+
+    const BRIDGE: Address = address!("0x8315177aB297bA92A06054cE80a67Ed4DBd7ed3a");
+    const INBOX: Address = address!("0x4Dbd4fc535Ac27206064B68FfCf827b0A60BAB3f");
+    const SENDER: Address = address!("0x3333333333333333333333333333333333333333");
+
+    fn delivered_logs(index: u64, kind: MessageType, data: &[u8]) -> [Log; 2] {
+        let bridge = MessageDelivered {
+            messageIndex: U256::from(index),
+            beforeInboxAcc: B256::ZERO,
+            inbox: INBOX,
+            kind: kind.to_u8(),
+            sender: SENDER,
+            messageDataHash: keccak256(data),
+            baseFeeL1: U256::from(10),
+            timestamp: 1_700_000_000,
+        };
+        let inbox = InboxMessageDelivered {
+            messageNum: U256::from(index),
+            data: Bytes::copy_from_slice(data),
+        };
+        [
+            Log {
+                inner: alloy_primitives::Log::new_from_event_unchecked(BRIDGE, bridge)
+                    .reserialize(),
+                ..Default::default()
+            },
+            Log {
+                inner: alloy_primitives::Log::new_from_event_unchecked(INBOX, inbox).reserialize(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn retryable_log(event: impl SolEvent, block: u64) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: ARB_RETRYABLE_TX,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block),
+            transaction_hash: Some(B256::with_last_byte(block as u8)),
+            ..Default::default()
+        }
+    }
+
+    fn redeem_scheduled(
+        ticket_id: B256,
+        retry_tx: B256,
+        sequence_num: u64,
+    ) -> ArbRetryableTx::RedeemScheduled {
+        ArbRetryableTx::RedeemScheduled {
+            ticketId: ticket_id,
+            retryTxHash: retry_tx,
+            sequenceNum: sequence_num,
+            donatedGas: 100_000,
+            gasDonor: SENDER,
+            maxRefund: U256::from(1_000),
+            submissionFeeRefund: U256::ZERO,
+        }
+    }
+
+    fn l2_receipt(
+        tx_hash: B256,
+        block: u64,
+        success: bool,
+        logs: Vec<Log>,
+    ) -> ArbTransactionReceipt {
+        ArbTransactionReceipt {
+            inner: TransactionReceipt {
+                inner: ArbReceiptEnvelope::Retry(ReceiptWithBloom {
+                    receipt: ArbReceipt::new(Receipt {
+                        status: Eip658Value::Eip658(success),
+                        cumulative_gas_used: 0,
+                        logs,
+                    }),
+                    logs_bloom: Bloom::ZERO,
+                }),
+                transaction_hash: tx_hash,
+                transaction_index: Some(0),
+                block_hash: Some(B256::ZERO),
+                block_number: Some(block),
+                gas_used: 0,
+                effective_gas_price: 0,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                from: SENDER,
+                to: None,
+                contract_address: None,
+            },
+            gas_used_for_l1: 0,
+            l1_block_number: None,
+            timeboosted: None,
+        }
+    }
+
+    /// Returns the receipt as JSON, as a node would return it.
+    fn l2_receipt_json(tx_hash: B256, block: u64, success: bool) -> serde_json::Value {
+        serde_json::to_value(l2_receipt(tx_hash, block, success, Vec::new())).unwrap()
+    }
+
+    #[test]
+    fn submissions_are_decoded_from_l1_receipt() {
+        let params = RetryableSubmission {
+            to: TxKind::Call(address!("0x5555555555555555555555555555555555555555")),
+            l2_call_value: U256::from(1_000),
+            deposit_value: U256::from(10_000_000),
+            max_submission_fee: U256::from(20_000),
+            excess_fee_refund_address: SENDER,
+            call_value_refund_address: SENDER,
+            gas_limit: 100_000,
+            max_fee_per_gas: U256::from(50),
+            data: Bytes::from_static(&[0xde, 0xad]),
+        };
+        let mut data = (
+            params.to.to().copied().unwrap_or_default(),
+            params.l2_call_value,
+            params.deposit_value,
+            params.max_submission_fee,
+            params.excess_fee_refund_address,
+            params.call_value_refund_address,
+            U256::from(params.gas_limit),
+            params.max_fee_per_gas,
+            U256::from(params.data.len()),
+        )
+            .abi_encode_params();
+        data.extend_from_slice(&params.data);
+
+        let mut deposit = SENDER.to_vec();
+        deposit.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+        let [deposit_bridge, deposit_inbox] = delivered_logs(4, MessageType::EthDeposit, &deposit);
+        let [bridge, inbox] = delivered_logs(5, MessageType::SubmitRetryable, &data);
+        let receipt = TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: Eip658Value::Eip658(true),
+                    cumulative_gas_used: 0,
+                    logs: vec![deposit_bridge, deposit_inbox, inbox, bridge],
+                },
+                logs_bloom: Bloom::ZERO,
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: Some(0),
+            block_hash: None,
+            block_number: Some(19_000_000),
+            gas_used: 0,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: SENDER,
+            to: Some(INBOX),
+            contract_address: None,
+        };
+
+        let submissions = retryable_submissions(&receipt, BRIDGE, INBOX, 412346).unwrap();
+        let predicted = InboxSubmission::new(412346, 5, SENDER, U256::from(10)).retryable(&params);
+        assert_eq!(submissions.len(), 1);
+        assert_eq!(submissions[0].tx_hash(), predicted.ticket_id);
+    }
+
+    #[tokio::test]
+    async fn status_follows_ticket_through_retries() {
+        let asserter = Asserter::new();
+        let tracker = RetryableTracker::new(
+            ProviderBuilder::<_, _, Arbitrum>::default().connect_mocked_client(asserter.clone()),
+        )
+        .with_log_block_range(8);
+        let ticket_id = B256::repeat_byte(0x77);
+        let auto_redeem = B256::repeat_byte(0x78);
+        let manual_redeem = B256::repeat_byte(0x79);
+
+        // Auto-redeem failed, a manual redeem in a later block succeeded. Blocks 10 to 20 are
+        // read as 10..=17 and 18..=20.
+        asserter.push_success(&l2_receipt_json(ticket_id, 10, true));
+        asserter.push_success(&U64::from(20));
+        asserter.push_success(&vec![
+            retryable_log(
+                ArbRetryableTx::TicketCreated {
+                    ticketId: ticket_id,
+                },
+                10,
+            ),
+            retryable_log(redeem_scheduled(ticket_id, auto_redeem, 0), 10),
+            retryable_log(
+                ArbRetryableTx::LifetimeExtended {
+                    ticketId: ticket_id,
+                    newTimeout: U256::from(1_900_000_000u64),
+                },
+                12,
+            ),
+            retryable_log(redeem_scheduled(ticket_id, manual_redeem, 1), 15),
+        ]);
+        asserter.push_success(&vec![retryable_log(
+            ArbRetryableTx::LifetimeExtended {
+                ticketId: ticket_id,
+                newTimeout: U256::from(2_000_000_000u64),
+            },
+            19,
+        )]);
+        asserter.push_success(&l2_receipt_json(auto_redeem, 10, false));
+        asserter.push_success(&l2_receipt_json(manual_redeem, 15, true));
+        assert_eq!(
+            tracker.status(ticket_id).await.unwrap(),
+            RetryableStatus::Redeemed {
+                retry_tx: manual_redeem,
+                auto_redeem: false,
+            }
+        );
+
+        // The auto-redeem succeeded.
+        asserter.push_success(&l2_receipt_json(ticket_id, 10, true));
+        asserter.push_success(&U64::from(10));
+        asserter.push_success(&vec![retryable_log(
+            redeem_scheduled(ticket_id, auto_redeem, 0),
+            10,
+        )]);
+        asserter.push_success(&l2_receipt_json(auto_redeem, 10, true));
+        assert_eq!(
+            tracker.status(ticket_id).await.unwrap(),
+            RetryableStatus::Redeemed {
+                retry_tx: auto_redeem,
+                auto_redeem: true,
+            }
+        );
+
+        // Auto-redeem failed and a keepalive extended the ticket, which is still alive.
+        asserter.push_success(&l2_receipt_json(ticket_id, 10, true));
+        asserter.push_success(&U64::from(12));
+        asserter.push_success(&vec![
+            retryable_log(redeem_scheduled(ticket_id, auto_redeem, 0), 10),
+            retryable_log(
+                ArbRetryableTx::LifetimeExtended {
+                    ticketId: ticket_id,
+                    newTimeout: U256::from(1_800_000_000u64),
+                },
+                12,
+            ),
+        ]);
+        asserter.push_success(&l2_receipt_json(auto_redeem, 10, false));
+        asserter.push_success(&Bytes::from(U256::from(1_800_000_000u64).abi_encode()));
+        assert_eq!(
+            tracker.status(ticket_id).await.unwrap(),
+            RetryableStatus::Redeemable {
+                timeout: 1_800_000_000,
+                failed_retries: vec![auto_redeem],
+                keepalives: vec![B256::with_last_byte(12)],
+            }
+        );
+
+        // Cancelled by the beneficiary.
+        asserter.push_success(&l2_receipt_json(ticket_id, 10, true));
+        asserter.push_success(&U64::from(17));
+        asserter.push_success(&vec![retryable_log(
+            ArbRetryableTx::Canceled {
+                ticketId: ticket_id,
+            },
+            16,
+        )]);
+        assert_eq!(
+            tracker.status(ticket_id).await.unwrap(),
+            RetryableStatus::Canceled {
+                cancel_tx: B256::with_last_byte(16)
+            }
+        );
+
+        // Expired: getTimeout reverts with NoTicketWithID.
+        let revert = Bytes::from(ArbRetryableTx::NoTicketWithID {}.abi_encode());
+        asserter.push_success(&l2_receipt_json(ticket_id, 10, true));
+        asserter.push_success(&U64::from(10));
+        asserter.push_success(&Vec::<Log>::new());
+        asserter.push_failure(
+            serde_json::from_value(serde_json::json!({
+                "code": 3,
+                "message": "execution reverted",
+                "data": revert,
+            }))
+            .unwrap(),
+        );
+        assert_eq!(
+            tracker.status(ticket_id).await.unwrap(),
+            RetryableStatus::Expired {
+                failed_retries: Vec::new()
+            }
+        );
+
+        // Any other error is returned.
+        asserter.push_success(&l2_receipt_json(ticket_id, 10, true));
+        asserter.push_success(&U64::from(10));
+        asserter.push_success(&Vec::<Log>::new());
+        asserter.push_failure_msg("execution reverted");
+        assert!(matches!(
+            tracker.status(ticket_id).await,
+            Err(RetryableError::Transport(_))
+        ));
+
+        asserter.push_success(&Option::<ArbTransactionReceipt>::None);
+        assert_eq!(
+            tracker.status(ticket_id).await.unwrap(),
+            RetryableStatus::NotCreated
+        );
+    }
+}
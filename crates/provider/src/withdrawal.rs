@@ -0,0 +1,567 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use alloy_core::{
+    sol,
+    sol_types::{SolCall, SolEvent, SolValue},
+};
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::{Address, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::{BlockNumberOrTag, Filter, Log, TransactionRequest};
+use alloy_transport::TransportError;
+use arb_alloy_consensus::{
+    ArbHeaderDecodeError, ArbHeaderInfo, L2ToL1Send, OutboxProof, outbox::OutboxError,
+};
+use arb_alloy_network::Arbitrum;
+use arb_alloy_precompiles::{
+    ArbSys, NodeInterface,
+    addresses::{ARB_SYS, NODE_INTERFACE},
+};
+use arb_alloy_rpc_types::ArbTransactionReceipt;
+
+// Nitro reference
+// - precompiles/ArbSys.go: SendTxToL1() emits `L2ToL1Tx` with the send hash and its leaf
+//   position in the send Merkle accumulator
+// - nodeInterface/NodeInterface.go: ConstructOutboxProof(size, leaf)
+// - contracts/src/rollup/RollupCore.sol: latestConfirmed() and the `NodeConfirmed` event,
+//   or `AssertionConfirmed` on BoLD rollups; both carry the confirmed L2 block hash and
+//   send root
+// - contracts/src/bridge/Outbox.sol: isSpent(), executeTransaction()
+sol! {
+    interface IOutbox {
+        function isSpent(uint256 index) external view returns (bool);
+
+        function executeTransaction(
+            bytes32[] calldata proof,
+            uint256 index,
+            address l2Sender,
+            address to,
+            uint256 l2Block,
+            uint256 l1Block,
+            uint256 l2Timestamp,
+            uint256 value,
+            bytes calldata data
+        ) external;
+    }
+
+    interface IRollupCore {
+        /// Returns the latest confirmed node number, or assertion hash on BoLD rollups.
+        function latestConfirmed() external view returns (bytes32);
+
+        event NodeConfirmed(uint64 indexed nodeNum, bytes32 blockHash, bytes32 sendRoot);
+
+        event AssertionConfirmed(bytes32 indexed assertionHash, bytes32 blockHash, bytes32 sendRoot);
+    }
+}
+
+/// Error while tracking a withdrawal.
+#[derive(Debug)]
+pub enum WithdrawalError {
+    /// An RPC request failed.
+    Transport(TransportError),
+    /// A contract call returned malformed data.
+    Abi(alloy_core::sol_types::Error),
+    /// The L2 node does not know the confirmed block.
+    BlockNotFound(B256),
+    /// The L2 node returned a header that does not hash to the confirmed block hash.
+    BlockHashMismatch {
+        /// Block hash confirmed on L1.
+        expected: B256,
+        /// Hash of the returned header.
+        got: B256,
+    },
+    /// The confirmed block header does not carry Arbitrum fields.
+    Header(ArbHeaderDecodeError),
+    /// The confirmed block header commits to a different send root than the rollup.
+    SendRootMismatch {
+        /// Send root confirmed on L1.
+        expected: B256,
+        /// Send root in the L2 header.
+        got: B256,
+    },
+    /// The withdrawal is not covered by a confirmed send root yet.
+    NotConfirmed {
+        /// Leaf position of the withdrawal.
+        position: u64,
+    },
+    /// The proof is for a different send than the withdrawal.
+    SendMismatch {
+        /// Hash of the withdrawal.
+        expected: B256,
+        /// Send hash returned with the proof.
+        got: B256,
+    },
+    /// The proof does not verify against the confirmed send root.
+    Proof(OutboxError),
+}
+
+impl fmt::Display for WithdrawalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "RPC request failed: {err}"),
+            Self::Abi(err) => write!(f, "invalid contract return data: {err}"),
+            Self::BlockNotFound(hash) => write!(f, "confirmed L2 block {hash} not found"),
+            Self::BlockHashMismatch { expected, got } => write!(
+                f,
+                "L2 node returned header {got} for confirmed block {expected}"
+            ),
+            Self::Header(err) => write!(f, "invalid confirmed L2 header: {err}"),
+            Self::SendRootMismatch { expected, got } => write!(
+                f,
+                "confirmed L2 header has send root {got}, rollup confirmed {expected}"
+            ),
+            Self::NotConfirmed { position } => {
+                write!(f, "withdrawal {position} is not confirmed yet")
+            }
+            Self::SendMismatch { expected, got } => {
+                write!(f, "outbox proof is for send {got}, expected {expected}")
+            }
+            Self::Proof(err) => write!(f, "invalid outbox proof: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for WithdrawalError {}
+
+impl From<TransportError> for WithdrawalError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl From<alloy_core::sol_types::Error> for WithdrawalError {
+    fn from(err: alloy_core::sol_types::Error) -> Self {
+        Self::Abi(err)
+    }
+}
+
+/// L2-to-L1 message sent through `ArbSys.withdrawEth` or `ArbSys.sendTxToL1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Withdrawal {
+    /// Leaf position of the send in the send Merkle accumulator.
+    pub position: u64,
+    /// The send, as hashed into the accumulator.
+    pub send: L2ToL1Send,
+}
+
+impl Withdrawal {
+    /// Decodes an `ArbSys` `L2ToL1Tx` log, returning `None` for any other log.
+    pub fn from_log(log: &Log) -> Option<Self> {
+        if log.address() != ARB_SYS {
+            return None;
+        }
+        let event = ArbSys::L2ToL1Tx::decode_log(&log.inner).ok()?.data;
+        Some(Self {
+            position: event.position.saturating_to(),
+            send: L2ToL1Send {
+                caller: event.caller,
+                destination: event.destination,
+                arb_block_num: event.arbBlockNum.saturating_to(),
+                eth_block_num: event.ethBlockNum.saturating_to(),
+                timestamp: event.timestamp.saturating_to(),
+                callvalue: event.callvalue,
+                data: event.data,
+            },
+        })
+    }
+
+    /// Returns the withdrawals made by an L2 transaction.
+    pub fn from_receipt(receipt: &ArbTransactionReceipt) -> Vec<Self> {
+        receipt
+            .inner
+            .logs()
+            .iter()
+            .filter_map(Self::from_log)
+            .collect()
+    }
+
+    /// Returns the send hash, the leaf the outbox proves.
+    pub fn hash(&self) -> B256 {
+        self.send.hash()
+    }
+}
+
+/// Stage of a withdrawal on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// The withdrawal is not covered by a confirmed send root.
+    Unconfirmed,
+    /// The withdrawal can be executed on the outbox.
+    Confirmed,
+    /// The withdrawal was executed on the outbox.
+    Executed,
+}
+
+/// Latest L2 state confirmed by the rollup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmedState {
+    /// Hash of the confirmed L2 block.
+    pub block_hash: B256,
+    /// Arbitrum fields of the confirmed block header.
+    pub info: ArbHeaderInfo,
+}
+
+/// Tracks withdrawals from L2 to their execution on the L1 outbox.
+#[derive(Debug, Clone)]
+pub struct WithdrawalTracker<L1, L2> {
+    l1: L1,
+    l2: L2,
+    outbox: Address,
+    rollup: Address,
+    l1_from_block: u64,
+}
+
+impl<L1, L2> WithdrawalTracker<L1, L2>
+where
+    L1: Provider<Ethereum>,
+    L2: Provider<Arbitrum>,
+{
+    /// Creates a tracker for the chain whose L1 contracts are `outbox` and `rollup`.
+    ///
+    /// Rollup confirmations are searched from `l1_from_block`, such as the rollup deployment
+    /// block. Most RPC providers reject log queries over the whole chain.
+    pub const fn new(l1: L1, l2: L2, outbox: Address, rollup: Address, l1_from_block: u64) -> Self {
+        Self {
+            l1,
+            l2,
+            outbox,
+            rollup,
+            l1_from_block,
+        }
+    }
+
+    /// Sets the first L1 block searched for rollup confirmations.
+    pub const fn with_l1_from_block(mut self, block: u64) -> Self {
+        self.l1_from_block = block;
+        self
+    }
+
+    async fn call_l1<C: SolCall>(
+        &self,
+        to: Address,
+        call: C,
+    ) -> Result<C::Return, WithdrawalError> {
+        let tx = TransactionRequest::default()
+            .with_to(to)
+            .with_input(call.abi_encode());
+        Ok(C::abi_decode_returns(&self.l1.call(tx).await?)?)
+    }
+
+    /// Returns the latest L2 state confirmed by the rollup, checked against the L2 header.
+    ///
+    /// Returns `None` when no confirmation is found from the configured L1 block.
+    pub async fn latest_confirmed(&self) -> Result<Option<ConfirmedState>, WithdrawalError> {
+        let confirmed = self
+            .call_l1(self.rollup, IRollupCore::latestConfirmedCall {})
+            .await?;
+        let filter = Filter::new()
+            .address(self.rollup)
+            .event_signature(Vec::from([
+                IRollupCore::NodeConfirmed::SIGNATURE_HASH,
+                IRollupCore::AssertionConfirmed::SIGNATURE_HASH,
+            ]))
+            .topic1(confirmed)
+            .from_block(self.l1_from_block)
+            .to_block(BlockNumberOrTag::Latest);
+        let Some(log) = self.l1.get_logs(&filter).await?.pop() else {
+            return Ok(None);
+        };
+        let (block_hash, send_root) = <(B256, B256)>::abi_decode_params(&log.inner.data.data)
+            .map_err(WithdrawalError::Abi)?;
+
+        let block = self
+            .l2
+            .get_block_by_hash(block_hash)
+            .await?
+            .ok_or(WithdrawalError::BlockNotFound(block_hash))?;
+        // The header fields are only trusted once they hash to the L1-confirmed block.
+        let got = block.header.inner.hash_slow();
+        if got != block_hash {
+            return Err(WithdrawalError::BlockHashMismatch {
+                expected: block_hash,
+                got,
+            });
+        }
+        let info =
+            ArbHeaderInfo::decode_header(&block.header.inner).map_err(WithdrawalError::Header)?;
+        if info.send_root != send_root {
+            return Err(WithdrawalError::SendRootMismatch {
+                expected: send_root,
+                got: info.send_root,
+            });
+        }
+        Ok(Some(ConfirmedState { block_hash, info }))
+    }
+
+    /// Returns whether the outbox executed the withdrawal.
+    pub async fn is_executed(&self, withdrawal: &Withdrawal) -> Result<bool, WithdrawalError> {
+        self.call_l1(
+            self.outbox,
+            IOutbox::isSpentCall {
+                index: U256::from(withdrawal.position),
+            },
+        )
+        .await
+    }
+
+    /// Returns the stage of a withdrawal.
+    pub async fn status(
+        &self,
+        withdrawal: &Withdrawal,
+    ) -> Result<WithdrawalStatus, WithdrawalError> {
+        if self.is_executed(withdrawal).await? {
+            return Ok(WithdrawalStatus::Executed);
+        }
+        Ok(match self.latest_confirmed().await? {
+            Some(state) if withdrawal.position < state.info.send_count => {
+                WithdrawalStatus::Confirmed
+            }
+            _ => WithdrawalStatus::Unconfirmed,
+        })
+    }
+
+    /// Fetches the outbox proof of a confirmed withdrawal and verifies it locally against the
+    /// confirmed send root.
+    pub async fn proof(&self, withdrawal: &Withdrawal) -> Result<OutboxProof, WithdrawalError> {
+        let not_confirmed = WithdrawalError::NotConfirmed {
+            position: withdrawal.position,
+        };
+        let state = self.latest_confirmed().await?.ok_or(not_confirmed)?;
+        if withdrawal.position >= state.info.send_count {
+            return Err(WithdrawalError::NotConfirmed {
+                position: withdrawal.position,
+            });
+        }
+
+        let call = NodeInterface::constructOutboxProofCall {
+            size: state.info.send_count,
+            leaf: withdrawal.position,
+        };
+        let tx = <Arbitrum as Network>::TransactionRequest::default()
+            .with_to(NODE_INTERFACE)
+            .with_input(call.abi_encode());
+        let ret =
+            NodeInterface::constructOutboxProofCall::abi_decode_returns(&self.l2.call(tx).await?)?;
+        let proof = OutboxProof {
+            send: ret.send,
+            root: ret.root,
+            proof: ret.proof,
+            index: withdrawal.position,
+        };
+
+        let expected = withdrawal.hash();
+        if proof.send != expected {
+            return Err(WithdrawalError::SendMismatch {
+                expected,
+                got: proof.send,
+            });
+        }
+        proof
+            .verify_header(&state.info)
+            .map_err(WithdrawalError::Proof)?;
+        Ok(proof)
+    }
+
+    /// Returns the L1 `Outbox.executeTransaction` call for a withdrawal and its proof.
+    pub fn execute_transaction(
+        &self,
+        withdrawal: &Withdrawal,
+        proof: &OutboxProof,
+    ) -> TransactionRequest {
+        let send = &withdrawal.send;
+        let call = IOutbox::executeTransactionCall {
+            proof: proof.proof.clone(),
+            index: U256::from(withdrawal.position),
+            l2Sender: send.caller,
+            to: send.destination,
+            l2Block: U256::from(send.arb_block_num),
+            l1Block: U256::from(send.eth_block_num),
+            l2Timestamp: U256::from(send.timestamp),
+            value: send.callvalue,
+            data: send.data.clone(),
+        };
+        TransactionRequest::default()
+            .with_to(self.outbox)
+            .with_input(call.abi_encode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, LogData, address, keccak256};
+    use alloy_provider::ProviderBuilder;
+    use alloy_transport::mock::Asserter;
+
+    const OUTBOX: Address = address!("0x0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840");
+    const ROLLUP: Address = address!("0x5eF0D09d1E6204141B4d37530808eD19f60FBa35");
+
+    fn withdrawal() -> Withdrawal {
+        Withdrawal {
+            position: 1,
+            send: L2ToL1Send {
+                caller: address!("0x1111111111111111111111111111111111111111"),
+                destination: address!("0x1111111111111111111111111111111111111111"),
+                arb_block_num: 100,
+                eth_block_num: 20,
+                timestamp: 1_700_000_000,
+                callvalue: U256::from(1_000),
+                data: Bytes::new(),
+            },
+        }
+    }
+
+    fn confirmed_log(block_hash: B256, send_root: B256) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: ROLLUP,
+                data: LogData::new_unchecked(
+                    vec![
+                        IRollupCore::AssertionConfirmed::SIGNATURE_HASH,
+                        B256::repeat_byte(0xaa),
+                    ],
+                    (block_hash, send_root).abi_encode_params().into(),
+                ),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn withdrawal_decodes_from_arbsys_log() {
+        let w = withdrawal();
+        let event = ArbSys::L2ToL1Tx {
+            caller: w.send.caller,
+            destination: w.send.destination,
+            hash: w.hash().into(),
+            position: U256::from(w.position),
+            arbBlockNum: U256::from(w.send.arb_block_num),
+            ethBlockNum: U256::from(w.send.eth_block_num),
+            timestamp: U256::from(w.send.timestamp),
+            callvalue: w.send.callvalue,
+            data: w.send.data.clone(),
+        };
+        let log = Log {
+            inner: alloy_primitives::Log::new_from_event_unchecked(ARB_SYS, event.clone())
+                .reserialize(),
+            ..Default::default()
+        };
+        assert_eq!(Withdrawal::from_log(&log), Some(w));
+
+        let other = Log {
+            inner: alloy_primitives::Log::new_from_event_unchecked(OUTBOX, event).reserialize(),
+            ..Default::default()
+        };
+        assert_eq!(Withdrawal::from_log(&other), None);
+    }
+
+    #[tokio::test]
+    async fn tracker_reports_status_and_verifies_proof() {
+        let l1_asserter = Asserter::new();
+        let l2_asserter = Asserter::new();
+        let tracker = WithdrawalTracker::new(
+            ProviderBuilder::default().connect_mocked_client(l1_asserter.clone()),
+            ProviderBuilder::<_, _, Arbitrum>::default().connect_mocked_client(l2_asserter.clone()),
+            OUTBOX,
+            ROLLUP,
+            19_000_000,
+        );
+
+        let w = withdrawal();
+        let sibling = B256::repeat_byte(0x42);
+        let send_root = keccak256([sibling, keccak256(w.hash())].concat());
+        let info = ArbHeaderInfo {
+            send_root,
+            send_count: 2,
+            l1_block_number: 20,
+            arbos_format_version: 32,
+        };
+        let mut header = alloy_consensus::Header::default();
+        info.update_header(&mut header);
+        let block_hash = header.hash_slow();
+        let block = alloy_rpc_types_eth::Block::<arb_alloy_rpc_types::ArbTransaction>::empty(
+            alloy_rpc_types_eth::Header::new(header),
+        );
+
+        let push_confirmed = |count: usize| {
+            for _ in 0..count {
+                l1_asserter.push_success(&Bytes::from(B256::repeat_byte(0xaa).abi_encode()));
+                l1_asserter.push_success(&vec![confirmed_log(block_hash, send_root)]);
+                l2_asserter.push_success(&block);
+            }
+        };
+
+        l1_asserter.push_success(&Bytes::from(false.abi_encode()));
+        push_confirmed(1);
+        assert_eq!(
+            tracker.status(&w).await.unwrap(),
+            WithdrawalStatus::Confirmed
+        );
+
+        push_confirmed(1);
+        l2_asserter.push_success(&Bytes::from(
+            (w.hash(), send_root, vec![sibling]).abi_encode_params(),
+        ));
+        let proof = tracker.proof(&w).await.unwrap();
+        assert_eq!(proof.proof, vec![sibling]);
+
+        let tx = tracker.execute_transaction(&w, &proof);
+        let call = IOutbox::executeTransactionCall::abi_decode(tx.input.input().unwrap()).unwrap();
+        assert_eq!(call.index, U256::from(1));
+        assert_eq!(call.value, U256::from(1_000));
+
+        let unconfirmed = Withdrawal {
+            position: 2,
+            ..w.clone()
+        };
+        l1_asserter.push_success(&Bytes::from(false.abi_encode()));
+        push_confirmed(1);
+        assert_eq!(
+            tracker.status(&unconfirmed).await.unwrap(),
+            WithdrawalStatus::Unconfirmed
+        );
+
+        push_confirmed(1);
+        l2_asserter.push_success(&Bytes::from(
+            (w.hash(), send_root, vec![B256::ZERO]).abi_encode_params(),
+        ));
+        assert!(matches!(
+            tracker.proof(&w).await,
+            Err(WithdrawalError::Proof(OutboxError::RootMismatch { .. }))
+        ));
+
+        l1_asserter.push_success(&Bytes::from(true.abi_encode()));
+        assert_eq!(
+            tracker.status(&w).await.unwrap(),
+            WithdrawalStatus::Executed
+        );
+
+        // A node serving the confirmed send root with an inflated send count is caught by the
+        // block hash, even when it reports the confirmed hash for the forged header.
+        let mut forged = alloy_consensus::Header::default();
+        ArbHeaderInfo {
+            send_count: 10,
+            ..info
+        }
+        .update_header(&mut forged);
+        let forged_hash = forged.hash_slow();
+        let forged = alloy_rpc_types_eth::Block::<arb_alloy_rpc_types::ArbTransaction>::empty(
+            alloy_rpc_types_eth::Header {
+                hash: block_hash,
+                inner: forged,
+                total_difficulty: None,
+                size: None,
+            },
+        );
+        l1_asserter.push_success(&Bytes::from(false.abi_encode()));
+        l1_asserter.push_success(&Bytes::from(B256::repeat_byte(0xaa).abi_encode()));
+        l1_asserter.push_success(&vec![confirmed_log(block_hash, send_root)]);
+        l2_asserter.push_success(&forged);
+        assert!(matches!(
+            tracker.status(&unconfirmed).await,
+            Err(WithdrawalError::BlockHashMismatch { expected, got })
+                if expected == block_hash && got == forged_hash
+        ));
+    }
+}
@@ -1,10 +1,13 @@
+use alloc::vec::Vec;
 use alloy_consensus::TxReceipt;
+use alloy_core::sol_types::SolEventInterface;
 use alloy_network_primitives::ReceiptResponse;
 use alloy_primitives::{Address, B256, BlockHash, TxHash};
 use serde::{Deserialize, Serialize};
 
 use alloy_rpc_types_eth::Log as RpcLog;
 use arb_alloy_consensus::ArbReceiptEnvelope;
+use arb_alloy_precompiles::{ArbRetryableTx::ArbRetryableTxEvents, addresses::ARB_RETRYABLE_TX};
 
 /// Arbitrum transaction receipt response with Nitro extensions.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -31,6 +34,22 @@ pub struct ArbTransactionReceipt {
     pub timeboosted: Option<bool>,
 }
 
+impl ArbTransactionReceipt {
+    /// Returns the `ArbRetryableTx` events emitted by the transaction, in log order.
+    ///
+    /// Logs from other addresses and unknown events are skipped.
+    pub fn retryable_events(&self) -> Vec<ArbRetryableTxEvents> {
+        self.inner
+            .inner
+            .logs()
+            .iter()
+            .filter(|log| log.address() == ARB_RETRYABLE_TX)
+            .filter_map(|log| ArbRetryableTxEvents::decode_log(&log.inner).ok())
+            .map(|log| log.data)
+            .collect()
+    }
+}
+
 impl ReceiptResponse for ArbTransactionReceipt {
     fn contract_address(&self) -> Option<Address> {
         self.inner.contract_address
@@ -88,3 +107,83 @@ impl ReceiptResponse for ArbTransactionReceipt {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_consensus::{Eip658Value, Receipt, ReceiptWithBloom};
+    use alloy_core::sol_types::SolEvent;
+    use alloy_primitives::{Bloom, U256};
+    use arb_alloy_consensus::ArbReceipt;
+    use arb_alloy_precompiles::ArbRetryableTx;
+
+    use super::*;
+
+    fn retryable_log(event: impl SolEvent) -> RpcLog {
+        RpcLog {
+            inner: alloy_primitives::Log {
+                address: ARB_RETRYABLE_TX,
+                data: event.encode_log_data(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn receipt_decodes_retryable_events() {
+        let ticket_id = B256::repeat_byte(0x77);
+        let retry_tx = B256::repeat_byte(0x78);
+        let logs = vec![
+            retryable_log(ArbRetryableTx::TicketCreated {
+                ticketId: ticket_id,
+            }),
+            RpcLog::default(),
+            retryable_log(ArbRetryableTx::RedeemScheduled {
+                ticketId: ticket_id,
+                retryTxHash: retry_tx,
+                sequenceNum: 0,
+                donatedGas: 100_000,
+                gasDonor: Address::repeat_byte(0x33),
+                maxRefund: U256::from(1_000),
+                submissionFeeRefund: U256::ZERO,
+            }),
+        ];
+        let receipt = ArbTransactionReceipt {
+            inner: alloy_rpc_types_eth::TransactionReceipt {
+                inner: ArbReceiptEnvelope::SubmitRetryable(ReceiptWithBloom {
+                    receipt: ArbReceipt::new(Receipt {
+                        status: Eip658Value::Eip658(true),
+                        cumulative_gas_used: 0,
+                        logs,
+                    }),
+                    logs_bloom: Bloom::ZERO,
+                }),
+                transaction_hash: ticket_id,
+                transaction_index: Some(0),
+                block_hash: Some(B256::ZERO),
+                block_number: Some(10),
+                gas_used: 0,
+                effective_gas_price: 0,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                from: Address::repeat_byte(0x33),
+                to: None,
+                contract_address: None,
+            },
+            gas_used_for_l1: 0,
+            l1_block_number: None,
+            timeboosted: None,
+        };
+
+        let events = receipt.retryable_events();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            ArbRetryableTxEvents::TicketCreated(event) if event.ticketId == ticket_id
+        ));
+        assert!(matches!(
+            &events[1],
+            ArbRetryableTxEvents::RedeemScheduled(event)
+                if event.retryTxHash == retry_tx && event.sequenceNum == 0
+        ));
+    }
+}
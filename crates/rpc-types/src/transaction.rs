@@ -20,6 +20,15 @@ pub struct ArbTransaction {
     pub request_id: Option<B256>,
 }
 
+impl ArbTransaction {
+    /// Returns the unaliased L1 sender of an L1-originated transaction.
+    ///
+    /// See [`ArbTxEnvelope::l1_origin`].
+    pub fn l1_origin(&self) -> Option<Address> {
+        self.as_ref().l1_origin()
+    }
+}
+
 impl AsRef<ArbTxEnvelope> for ArbTransaction {
     fn as_ref(&self) -> &ArbTxEnvelope {
         self.inner.as_ref()
@@ -244,3 +253,65 @@ mod tx_serde {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arb_alloy_consensus::transactions::TxArbitrumLegacy;
+
+    use super::*;
+
+    #[test]
+    fn classic_tx_deserializes_from_rpc_json() {
+        // Synthetic values in the shape of Nitro's `eth_getTransactionByHash` response for a
+        // classic transaction, which leaves out a zero `effectiveGasPrice`.
+        let json = serde_json::json!({
+            "blockHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "blockNumber": "0x1dd5b",
+            "from": "0x2222222222222222222222222222222222222222",
+            "gas": "0x2dc6c0",
+            "gasPrice": "0x5b5c4a9e",
+            "hash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+            "input": "0x",
+            "nonce": "0x7",
+            "to": "0x4444444444444444444444444444444444444444",
+            "transactionIndex": "0x0",
+            "value": "0x0",
+            "type": "0x78",
+            "chainId": "0xa4b1",
+            "v": "0x14986",
+            "r": "0x5555555555555555555555555555555555555555555555555555555555555555",
+            "s": "0x1666666666666666666666666666666666666666666666666666666666666666",
+            "l1BlockNumber": "0xc0f4c3"
+        });
+        let tx: ArbTransaction = serde_json::from_value(json.clone()).unwrap();
+
+        let ArbTxEnvelope::ArbitrumLegacy(classic) = tx.as_ref() else {
+            panic!("expected a classic transaction, got {:?}", tx.as_ref());
+        };
+        assert_eq!(tx.ty(), 0x78);
+        assert_eq!(tx.tx_hash(), B256::repeat_byte(0x33));
+        assert_eq!(tx.from(), Address::repeat_byte(0x22));
+        assert_eq!(tx.block_number(), Some(0x1dd5b));
+        assert_eq!(classic.nonce, 7);
+        assert_eq!(classic.gas_price, 0x5b5c4a9e);
+        assert_eq!(classic.l1_block_number, 0xc0f4c3);
+        assert_eq!(classic.effective_gas_price, 0);
+        assert_eq!(classic.signature_chain_id(), Some(42161));
+
+        // The hash and L1 block number may be missing too.
+        let mut json = json;
+        for field in ["hash", "l1BlockNumber"] {
+            json.as_object_mut().unwrap().remove(field);
+        }
+        let tx: ArbTransaction = serde_json::from_value(json).unwrap();
+        let ArbTxEnvelope::ArbitrumLegacy(TxArbitrumLegacy {
+            hash_override,
+            l1_block_number,
+            ..
+        }) = tx.as_ref()
+        else {
+            panic!("expected a classic transaction");
+        };
+        assert_eq!((*hash_override, *l1_block_number), (B256::ZERO, 0));
+    }
+}
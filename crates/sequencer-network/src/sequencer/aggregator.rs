@@ -0,0 +1,661 @@
+//! Merges several sequencer feeds into one ordered stream.
+//!
+//! Every message is delivered once, from whichever source sends it first. Later copies are
+//! compared by their signed hash against the first one to catch sources that disagree, so
+//! sources that only format the same message differently do not conflict. The arrival order is
+//! recorded per source so the fastest endpoint can be picked.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, VecDeque},
+    string::String,
+    vec::Vec,
+};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use std::time::Instant;
+
+use alloy_primitives::{B256, keccak256};
+use futures_core::Stream;
+use futures_util::{
+    StreamExt,
+    stream::{BoxStream, SelectAll, select_all},
+};
+use tokio::time::{Sleep, sleep};
+
+use crate::sequencer::{
+    client::{FeedClient, FeedClientConfig, FeedClientError},
+    event::FeedEvent,
+    feed::BroadcastFeedMessage,
+};
+
+/// Settings for [`FeedAggregator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedAggregatorConfig {
+    /// Number of out-of-order messages held back while waiting for a missing sequence number
+    /// before it is reported as a gap.
+    pub reorder_window: usize,
+    /// Longest time messages are held back while waiting for a missing sequence number before
+    /// it is reported as a gap.
+    pub reorder_timeout: Duration,
+    /// Number of delivered sequence numbers remembered for duplicate and conflict detection.
+    pub history: usize,
+}
+
+impl FeedAggregatorConfig {
+    /// Creates a config with default limits.
+    pub const fn new() -> Self {
+        Self {
+            reorder_window: 64,
+            reorder_timeout: Duration::from_secs(2),
+            history: 4096,
+        }
+    }
+
+    /// Sets the number of messages held back while waiting for a missing one.
+    pub const fn with_reorder_window(mut self, reorder_window: usize) -> Self {
+        self.reorder_window = reorder_window;
+        self
+    }
+
+    /// Sets the longest time messages are held back while waiting for a missing one.
+    pub const fn with_reorder_timeout(mut self, reorder_timeout: Duration) -> Self {
+        self.reorder_timeout = reorder_timeout;
+        self
+    }
+
+    /// Sets the number of delivered sequence numbers remembered.
+    pub const fn with_history(mut self, history: usize) -> Self {
+        self.history = history;
+        self
+    }
+}
+
+impl Default for FeedAggregatorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Event yielded by a [`FeedAggregator`].
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum AggregatorEvent {
+    /// A new feed message, delivered in sequence order.
+    Message {
+        /// Index of the source that delivered the message first.
+        source: usize,
+        /// The message.
+        message: BroadcastFeedMessage,
+    },
+    /// No source delivered the sequence numbers from `expected` up to `received`.
+    Gap {
+        /// Sequence number that was expected next.
+        expected: u64,
+        /// Sequence number delivered instead.
+        received: u64,
+    },
+    /// A source sent a payload that differs from the one first delivered for the same
+    /// sequence number.
+    Conflict {
+        /// Conflicting sequence number.
+        sequence_number: u64,
+        /// Source whose payload was delivered.
+        first_source: usize,
+        /// Source that sent the differing payload.
+        source: usize,
+    },
+    /// A source confirmed a higher sequence number than any before.
+    ConfirmedSequenceNumber {
+        /// Highest confirmed sequence number.
+        sequence_number: u64,
+    },
+    /// A source lost its connection.
+    SourceDisconnected {
+        /// Index of the source.
+        source: usize,
+        /// Human-readable reason for the disconnect.
+        reason: String,
+        /// Delay before the source reconnects, or `None` if it gave up.
+        retry_in: Option<Duration>,
+    },
+}
+
+impl AggregatorEvent {
+    /// Converts the event into the equivalent single-feed [`FeedEvent`], if there is one.
+    pub fn into_feed_event(self) -> Option<FeedEvent> {
+        match self {
+            Self::Message { message, .. } => Some(FeedEvent::Message(message)),
+            Self::Gap { expected, received } => Some(FeedEvent::Gap { expected, received }),
+            Self::ConfirmedSequenceNumber { sequence_number } => {
+                Some(FeedEvent::ConfirmedSequenceNumber { sequence_number })
+            }
+            Self::Conflict { .. } | Self::SourceDisconnected { .. } => None,
+        }
+    }
+}
+
+/// Arrival statistics of one aggregated source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceStats {
+    /// Name the source was registered with.
+    pub name: String,
+    /// Messages received from the source.
+    pub messages: u64,
+    /// Messages the source delivered before every other source.
+    pub first_arrivals: u64,
+    /// Messages the source delivered after another source already had.
+    pub late_arrivals: u64,
+    /// Messages whose payload differed from the first delivered one.
+    pub conflicts: u64,
+    /// Summed delay of late arrivals behind the first arrival.
+    pub total_lag: Duration,
+}
+
+impl SourceStats {
+    /// Returns the average delay of late arrivals behind the first arrival.
+    pub fn mean_lag(&self) -> Option<Duration> {
+        let late = u32::try_from(self.late_arrivals).ok().filter(|&n| n > 0)?;
+        Some(self.total_lag / late)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Delivered {
+    source: usize,
+    payload: B256,
+    at: Instant,
+}
+
+/// Stream merging several feed sources into one ordered, deduplicated stream.
+pub struct FeedAggregator {
+    config: FeedAggregatorConfig,
+    sources: SelectAll<BoxStream<'static, (usize, FeedEvent)>>,
+    stats: Vec<SourceStats>,
+    next: Option<u64>,
+    pending: BTreeMap<u64, (usize, BroadcastFeedMessage)>,
+    delivered: BTreeMap<u64, Delivered>,
+    confirmed: Option<u64>,
+    queue: VecDeque<AggregatorEvent>,
+    /// Fires once held-back messages have waited `reorder_timeout` for a missing one.
+    reorder_deadline: Option<Pin<Box<Sleep>>>,
+}
+
+impl core::fmt::Debug for FeedAggregator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeedAggregator")
+            .field("config", &self.config)
+            .field("stats", &self.stats)
+            .field("next", &self.next)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FeedAggregator {
+    /// Creates an aggregator without sources.
+    pub fn new(config: FeedAggregatorConfig) -> Self {
+        Self {
+            config,
+            sources: select_all(Vec::new()),
+            stats: Vec::new(),
+            next: None,
+            pending: BTreeMap::new(),
+            delivered: BTreeMap::new(),
+            confirmed: None,
+            queue: VecDeque::new(),
+            reorder_deadline: None,
+        }
+    }
+
+    /// Subscribes to every feed in `configs`, naming each source after its URL.
+    ///
+    /// Fails if any feed config is invalid. Must be called from within a Tokio runtime.
+    pub fn connect(
+        config: FeedAggregatorConfig,
+        feeds: impl IntoIterator<Item = FeedClientConfig>,
+    ) -> Result<Self, FeedClientError> {
+        feeds
+            .into_iter()
+            .try_fold(Self::new(config), |aggregator, feed| {
+                let name = feed.url.clone();
+                Ok(aggregator.with_source(name, FeedClient::from_config(feed).subscribe()?))
+            })
+    }
+
+    /// Adds a source and returns the aggregator.
+    pub fn with_source<S>(mut self, name: impl Into<String>, events: S) -> Self
+    where
+        S: Stream<Item = FeedEvent> + Send + 'static,
+    {
+        self.add_source(name, events);
+        self
+    }
+
+    /// Adds a source and returns its index.
+    pub fn add_source<S>(&mut self, name: impl Into<String>, events: S) -> usize
+    where
+        S: Stream<Item = FeedEvent> + Send + 'static,
+    {
+        let index = self.stats.len();
+        self.stats.push(SourceStats {
+            name: name.into(),
+            ..Default::default()
+        });
+        self.sources
+            .push(events.map(move |event| (index, event)).boxed());
+        index
+    }
+
+    /// Returns the arrival statistics of every source, in registration order.
+    pub fn stats(&self) -> &[SourceStats] {
+        &self.stats
+    }
+
+    /// Returns the index of the source that delivered the most messages first.
+    pub fn fastest_source(&self) -> Option<usize> {
+        self.stats
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, stats)| stats.first_arrivals)
+            .map(|(index, _)| index)
+    }
+
+    fn observe(&mut self, source: usize, event: FeedEvent) {
+        match event {
+            FeedEvent::Message(message) => self.observe_message(source, message),
+            FeedEvent::ConfirmedSequenceNumber { sequence_number } => {
+                if self
+                    .confirmed
+                    .is_none_or(|confirmed| sequence_number > confirmed)
+                {
+                    self.confirmed = Some(sequence_number);
+                    self.queue
+                        .push_back(AggregatorEvent::ConfirmedSequenceNumber { sequence_number });
+                }
+            }
+            FeedEvent::Disconnected { reason, retry_in } => {
+                self.queue.push_back(AggregatorEvent::SourceDisconnected {
+                    source,
+                    reason,
+                    retry_in,
+                });
+            }
+            // Each source's own gaps and duplicates say nothing about the merged stream.
+            FeedEvent::Gap { .. } | FeedEvent::Duplicate { .. } => {}
+        }
+    }
+
+    fn observe_message(&mut self, source: usize, message: BroadcastFeedMessage) {
+        let now = Instant::now();
+        let sequence_number = message.sequence_number;
+        let payload = payload_hash(&message);
+        let stats = &mut self.stats[source];
+        stats.messages += 1;
+
+        if let Some(first) = self.delivered.get(&sequence_number).copied() {
+            stats.late_arrivals += 1;
+            stats.total_lag += now.saturating_duration_since(first.at);
+            if first.payload != payload {
+                stats.conflicts += 1;
+                self.queue.push_back(AggregatorEvent::Conflict {
+                    sequence_number,
+                    first_source: first.source,
+                    source,
+                });
+            }
+            return;
+        }
+        if self.next.is_some_and(|next| sequence_number < next) {
+            // Delivered long enough ago to have left the history.
+            stats.late_arrivals += 1;
+            return;
+        }
+
+        stats.first_arrivals += 1;
+        self.delivered.insert(
+            sequence_number,
+            Delivered {
+                source,
+                payload,
+                at: now,
+            },
+        );
+        self.pending.insert(sequence_number, (source, message));
+        self.release();
+    }
+
+    /// Emits pending messages that are next in sequence, skipping ahead once the reorder
+    /// window is exceeded.
+    ///
+    /// Starts the reorder timeout when messages are left waiting for a missing one.
+    fn release(&mut self) {
+        while let Some((&lowest, _)) = self.pending.first_key_value() {
+            if let Some(next) = self.next
+                && lowest != next
+            {
+                if self.pending.len() <= self.config.reorder_window {
+                    if self.reorder_deadline.is_none() {
+                        self.reorder_deadline = Some(Box::pin(sleep(self.config.reorder_timeout)));
+                    }
+                    break;
+                }
+                self.skip_gap();
+                continue;
+            }
+            self.emit_lowest();
+        }
+        if self.pending.is_empty() {
+            self.reorder_deadline = None;
+        }
+
+        if let Some(next) = self.next {
+            let keep_from = next.saturating_sub(self.config.history as u64);
+            self.delivered = self.delivered.split_off(&keep_from);
+        }
+    }
+
+    /// Emits the lowest pending message, ending any wait for the ones before it.
+    fn emit_lowest(&mut self) {
+        let Some((sequence_number, (source, message))) = self.pending.pop_first() else {
+            return;
+        };
+        self.queue
+            .push_back(AggregatorEvent::Message { source, message });
+        self.next = Some(sequence_number.saturating_add(1));
+        self.reorder_deadline = None;
+    }
+
+    /// Reports the missing sequence numbers below the lowest pending message as a gap and
+    /// emits that message.
+    fn skip_gap(&mut self) {
+        if let (Some(expected), Some((&received, _))) = (self.next, self.pending.first_key_value())
+        {
+            self.queue
+                .push_back(AggregatorEvent::Gap { expected, received });
+        }
+        self.emit_lowest();
+    }
+
+    /// Flushes held-back messages once every source has ended.
+    fn drain(&mut self) {
+        let window = self.config.reorder_window;
+        self.config.reorder_window = 0;
+        self.release();
+        self.config.reorder_window = window;
+    }
+}
+
+impl Stream for FeedAggregator {
+    type Item = AggregatorEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+            // Checked before polling the sources, so a source that is always ready, e.g. one
+            // replaying duplicates, cannot hold back the gap.
+            if self
+                .reorder_deadline
+                .as_ref()
+                .is_some_and(|deadline| deadline.deadline() <= tokio::time::Instant::now())
+            {
+                self.skip_gap();
+                self.release();
+                continue;
+            }
+            match self.sources.poll_next_unpin(cx) {
+                Poll::Ready(Some((source, event))) => self.observe(source, event),
+                Poll::Ready(None) => {
+                    self.drain();
+                    return Poll::Ready(self.queue.pop_front());
+                }
+                Poll::Pending => {
+                    let expired = self
+                        .reorder_deadline
+                        .as_mut()
+                        .is_some_and(|deadline| deadline.as_mut().poll(cx).is_ready());
+                    if !expired {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the hash identifying a message's payload.
+///
+/// This is the hash the sequencer signs, so two copies match exactly when they encode the same
+/// message. The chain id is left at zero since every source serves the same chain. Messages that
+/// cannot be RLP-encoded fall back to hashing their JSON.
+fn payload_hash(message: &BroadcastFeedMessage) -> B256 {
+    message.hash(0).unwrap_or_else(|_| {
+        keccak256(
+            serde_json::to_vec(&message.message_with_meta_data)
+                .expect("feed messages serialize to JSON"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+    use super::*;
+
+    fn source() -> (UnboundedSender<FeedEvent>, impl Stream<Item = FeedEvent>) {
+        let (tx, rx) = unbounded_channel();
+        let events = futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|event| (event, rx))
+        });
+        (tx, events)
+    }
+
+    fn msg(sequence_number: u64, delayed_messages_read: u64) -> FeedEvent {
+        let mut msg = BroadcastFeedMessage {
+            sequence_number,
+            ..Default::default()
+        };
+        msg.message_with_meta_data.delayed_messages_read = delayed_messages_read;
+        msg.message_with_meta_data.l1_incoming_message.header.sender =
+            "0xa4b000000000000000000073657175656e636572".into();
+        FeedEvent::Message(msg)
+    }
+
+    fn delivered(event: Option<AggregatorEvent>) -> (usize, u64) {
+        match event {
+            Some(AggregatorEvent::Message { source, message }) => (source, message.sequence_number),
+            other => panic!("expected a message, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn aggregator_orders_deduplicates_and_flags_conflicts() {
+        let (a, a_events) = source();
+        let (b, b_events) = source();
+        let mut aggregator =
+            FeedAggregator::new(FeedAggregatorConfig::new().with_reorder_window(1))
+                .with_source("a", a_events)
+                .with_source("b", b_events);
+
+        a.send(msg(1, 0)).unwrap();
+        assert_eq!(delivered(aggregator.next().await), (0, 1));
+
+        b.send(msg(1, 0)).unwrap();
+        b.send(msg(2, 0)).unwrap();
+        assert_eq!(delivered(aggregator.next().await), (1, 2));
+
+        a.send(msg(2, 9)).unwrap();
+        assert_eq!(
+            aggregator.next().await,
+            Some(AggregatorEvent::Conflict {
+                sequence_number: 2,
+                first_source: 1,
+                source: 0
+            })
+        );
+
+        // Held back until the missing message shows up from the other source.
+        a.send(msg(4, 0)).unwrap();
+        b.send(msg(3, 0)).unwrap();
+        assert_eq!(delivered(aggregator.next().await), (1, 3));
+        assert_eq!(delivered(aggregator.next().await), (0, 4));
+
+        a.send(FeedEvent::ConfirmedSequenceNumber { sequence_number: 3 })
+            .unwrap();
+        b.send(FeedEvent::ConfirmedSequenceNumber { sequence_number: 3 })
+            .unwrap();
+        a.send(msg(7, 0)).unwrap();
+        a.send(msg(8, 0)).unwrap();
+        assert_eq!(
+            aggregator.next().await,
+            Some(AggregatorEvent::ConfirmedSequenceNumber { sequence_number: 3 })
+        );
+        assert_eq!(
+            aggregator.next().await,
+            Some(AggregatorEvent::Gap {
+                expected: 5,
+                received: 7
+            })
+        );
+        assert_eq!(delivered(aggregator.next().await), (0, 7));
+        assert_eq!(delivered(aggregator.next().await), (0, 8));
+
+        drop((a, b));
+        assert_eq!(aggregator.next().await, None);
+
+        let stats = aggregator.stats();
+        assert_eq!(
+            (
+                stats[0].messages,
+                stats[0].first_arrivals,
+                stats[0].conflicts
+            ),
+            (5, 4, 1)
+        );
+        assert_eq!(
+            (
+                stats[1].messages,
+                stats[1].first_arrivals,
+                stats[1].late_arrivals
+            ),
+            (3, 2, 1)
+        );
+        assert!(stats[1].mean_lag().is_some());
+        assert_eq!(aggregator.fastest_source(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn aggregator_compares_payloads_by_signed_hash() {
+        let (a, a_events) = source();
+        let (b, b_events) = source();
+        let mut aggregator = FeedAggregator::new(FeedAggregatorConfig::new())
+            .with_source("a", a_events)
+            .with_source("b", b_events);
+
+        let FeedEvent::Message(mut first) = msg(1, 0) else {
+            unreachable!()
+        };
+        first
+            .message_with_meta_data
+            .l1_incoming_message
+            .header
+            .base_fee_l1 = serde_json::json!(10);
+        let mut second = first.clone();
+        second
+            .message_with_meta_data
+            .l1_incoming_message
+            .header
+            .base_fee_l1 = serde_json::json!("0xa");
+        a.send(FeedEvent::Message(first)).unwrap();
+        b.send(FeedEvent::Message(second)).unwrap();
+        a.send(msg(2, 0)).unwrap();
+
+        assert_eq!(delivered(aggregator.next().await), (0, 1));
+        assert_eq!(delivered(aggregator.next().await), (0, 2));
+        assert_eq!(aggregator.stats()[1].conflicts, 0);
+    }
+
+    #[tokio::test]
+    async fn aggregator_reports_gaps_after_reorder_timeout() {
+        let (a, a_events) = source();
+        let mut aggregator = FeedAggregator::new(
+            FeedAggregatorConfig::new().with_reorder_timeout(Duration::from_millis(50)),
+        )
+        .with_source("a", a_events);
+        a.send(msg(1, 0)).unwrap();
+        a.send(msg(3, 0)).unwrap();
+        a.send(msg(4, 0)).unwrap();
+        assert_eq!(delivered(aggregator.next().await), (0, 1));
+
+        // The source stays open, so only the timeout releases the held-back messages.
+        let started = Instant::now();
+        let gap = tokio::time::timeout(Duration::from_secs(5), aggregator.next())
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(
+            gap,
+            Some(AggregatorEvent::Gap {
+                expected: 2,
+                received: 3
+            })
+        );
+        assert_eq!(delivered(aggregator.next().await), (0, 3));
+        assert_eq!(delivered(aggregator.next().await), (0, 4));
+
+        a.send(msg(5, 0)).unwrap();
+        assert_eq!(delivered(aggregator.next().await), (0, 5));
+        drop(a);
+        assert_eq!(aggregator.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn aggregator_reports_gaps_while_a_source_replays_duplicates() {
+        // Never returns `Pending`, so the deadline has to be checked between events.
+        let flood = futures_util::stream::iter([msg(1, 0), msg(3, 0)])
+            .chain(futures_util::stream::repeat_with(|| msg(3, 0)));
+        let mut aggregator = FeedAggregator::new(
+            FeedAggregatorConfig::new().with_reorder_timeout(Duration::from_millis(50)),
+        )
+        .with_source("a", flood);
+        assert_eq!(delivered(aggregator.next().await), (0, 1));
+        assert_eq!(
+            aggregator.next().await,
+            Some(AggregatorEvent::Gap {
+                expected: 2,
+                received: 3
+            })
+        );
+        assert_eq!(delivered(aggregator.next().await), (0, 3));
+    }
+
+    #[tokio::test]
+    async fn aggregator_flushes_held_back_messages_when_sources_end() {
+        let (a, a_events) = source();
+        let mut aggregator =
+            FeedAggregator::new(FeedAggregatorConfig::new()).with_source("a", a_events);
+        a.send(msg(1, 0)).unwrap();
+        a.send(msg(3, 0)).unwrap();
+        drop(a);
+
+        let events: Vec<_> = aggregator
+            .by_ref()
+            .filter_map(|event| core::future::ready(event.into_feed_event()))
+            .collect()
+            .await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[1],
+            FeedEvent::Gap {
+                expected: 2,
+                received: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,709 @@
+//! Append-only on-disk archive of feed messages and a paced replayer.
+//!
+//! An archive is two files:
+//! - the data file: the magic bytes [`ARCHIVE_MAGIC`], then one record per message, each
+//!   `u64 sequence number ‖ u64 receive time (unix ms) ‖ u32 length ‖ JSON message`;
+//! - the index file (the data path with `.idx` appended): one `u64 sequence number ‖ u64 offset`
+//!   entry per record.
+//!
+//! All integers are big-endian. A missing index, or one that does not end at the last record
+//! of the data file, is rebuilt by scanning the data file. A record cut short by a crash is
+//! dropped when the archive is reopened for writing.
+
+use alloc::{string::ToString, vec::Vec};
+use core::{fmt, time::Duration};
+use std::{
+    ffi::OsString,
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+use tokio::{sync::mpsc, time::sleep};
+
+use crate::sequencer::{
+    client::FeedSubscription,
+    event::{FeedEvent, SequenceTracker},
+    feed::BroadcastFeedMessage,
+};
+
+/// Magic bytes opening every archive data file.
+pub const ARCHIVE_MAGIC: [u8; 8] = *b"ARBFEED1";
+
+const RECORD_HEADER_LEN: u64 = 20;
+const INDEX_ENTRY_LEN: usize = 16;
+
+/// Error while reading or writing a feed archive.
+#[derive(Debug)]
+pub enum FeedArchiveError {
+    /// Filesystem failure.
+    Io(io::Error),
+    /// A message could not be encoded or decoded.
+    Json(serde_json::Error),
+    /// The data file does not start with [`ARCHIVE_MAGIC`].
+    BadMagic,
+    /// A record or index entry is cut short.
+    Truncated {
+        /// Byte offset of the incomplete record in the data file.
+        offset: u64,
+    },
+    /// A message was appended with a sequence number not above the last archived one.
+    OutOfOrder {
+        /// Last archived sequence number.
+        last: u64,
+        /// Rejected sequence number.
+        received: u64,
+    },
+    /// No archived message has a sequence number at or after the requested one.
+    NotFound(u64),
+}
+
+impl fmt::Display for FeedArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "feed archive I/O error: {err}"),
+            Self::Json(err) => write!(f, "invalid archived feed message: {err}"),
+            Self::BadMagic => write!(f, "not a feed archive"),
+            Self::Truncated { offset } => write!(f, "feed archive truncated at offset {offset}"),
+            Self::OutOfOrder { last, received } => {
+                write!(
+                    f,
+                    "sequence number {received} does not follow archived {last}"
+                )
+            }
+            Self::NotFound(seq) => write!(f, "no archived message at or after {seq}"),
+        }
+    }
+}
+
+impl core::error::Error for FeedArchiveError {}
+
+impl From<io::Error> for FeedArchiveError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for FeedArchiveError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Archived feed message with the time it was received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveRecord {
+    /// Receive time in milliseconds since the Unix epoch.
+    pub received_at_ms: u64,
+    /// Archived message.
+    pub message: BroadcastFeedMessage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IndexEntry {
+    sequence_number: u64,
+    offset: u64,
+}
+
+/// Returns the index file path belonging to the archive at `path`.
+pub fn index_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".idx");
+    PathBuf::from(name)
+}
+
+/// Index entries of an archive, with the end of the last complete record.
+#[derive(Debug)]
+struct LoadedIndex {
+    entries: Vec<IndexEntry>,
+    /// Whether the entries were rebuilt from the data file.
+    rebuilt: bool,
+    /// Offset just past the last complete record.
+    end: u64,
+}
+
+/// Loads the index from its file, or rebuilds it from the data file when the index is missing
+/// or disagrees with the data file.
+fn load_index(path: &Path, data: &mut File) -> Result<LoadedIndex, FeedArchiveError> {
+    let mut magic = [0u8; 8];
+    data.seek(SeekFrom::Start(0))?;
+    data.read_exact(&mut magic)
+        .map_err(|_| FeedArchiveError::BadMagic)?;
+    if magic != ARCHIVE_MAGIC {
+        return Err(FeedArchiveError::BadMagic);
+    }
+
+    let bytes = match std::fs::read(index_path(path)) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return scan(data),
+        Err(err) => return Err(err.into()),
+    };
+    let entries: Vec<_> = bytes
+        .chunks_exact(INDEX_ENTRY_LEN)
+        .map(|entry| IndexEntry {
+            sequence_number: u64::from_be_bytes(entry[..8].try_into().unwrap()),
+            offset: u64::from_be_bytes(entry[8..].try_into().unwrap()),
+        })
+        .collect();
+    if bytes.len() % INDEX_ENTRY_LEN == 0 && index_matches(data, &entries)? {
+        Ok(LoadedIndex {
+            entries,
+            rebuilt: false,
+            end: data.metadata()?.len(),
+        })
+    } else {
+        scan(data)
+    }
+}
+
+/// Returns `true` if the index entries are ordered, start at the first record and the last one
+/// points at a record ending exactly at the end of the data file.
+fn index_matches(data: &mut File, entries: &[IndexEntry]) -> Result<bool, FeedArchiveError> {
+    let len = data.metadata()?.len();
+    let Some(last) = entries.last() else {
+        return Ok(len == ARCHIVE_MAGIC.len() as u64);
+    };
+    let ordered = entries.windows(2).all(|pair| {
+        pair[0].sequence_number < pair[1].sequence_number
+            && pair[0].offset + RECORD_HEADER_LEN <= pair[1].offset
+    });
+    if !ordered
+        || entries[0].offset != ARCHIVE_MAGIC.len() as u64
+        || last.offset.saturating_add(RECORD_HEADER_LEN) > len
+    {
+        return Ok(false);
+    }
+    let mut header = [0u8; RECORD_HEADER_LEN as usize];
+    data.seek(SeekFrom::Start(last.offset))?;
+    data.read_exact(&mut header)?;
+    let payload_len = u64::from(u32::from_be_bytes(header[16..].try_into().unwrap()));
+    let sequence_number = u64::from_be_bytes(header[..8].try_into().unwrap());
+    Ok(sequence_number == last.sequence_number
+        && last.offset + RECORD_HEADER_LEN + payload_len == len)
+}
+
+/// Rebuilds the index by walking the record headers of the data file.
+///
+/// Stops at a record cut short by the end of the file.
+fn scan(data: &mut File) -> Result<LoadedIndex, FeedArchiveError> {
+    let len = data.metadata()?.len();
+    let mut reader = BufReader::new(&mut *data);
+    let mut offset = ARCHIVE_MAGIC.len() as u64;
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut entries = Vec::new();
+    while offset + RECORD_HEADER_LEN <= len {
+        let mut header = [0u8; RECORD_HEADER_LEN as usize];
+        reader.read_exact(&mut header)?;
+        let payload_len = u64::from(u32::from_be_bytes(header[16..].try_into().unwrap()));
+        let next = offset + RECORD_HEADER_LEN + payload_len;
+        if next > len {
+            break;
+        }
+        entries.push(IndexEntry {
+            sequence_number: u64::from_be_bytes(header[..8].try_into().unwrap()),
+            offset,
+        });
+        reader.seek_relative(payload_len as i64)?;
+        offset = next;
+    }
+    Ok(LoadedIndex {
+        entries,
+        rebuilt: true,
+        end: offset,
+    })
+}
+
+fn write_index_entry(index: &mut impl Write, entry: IndexEntry) -> io::Result<()> {
+    index.write_all(&entry.sequence_number.to_be_bytes())?;
+    index.write_all(&entry.offset.to_be_bytes())
+}
+
+/// Appends feed messages to an archive.
+///
+/// Index entries are only written once the data they point at has been flushed, so a crash
+/// leaves the index behind the data file, never ahead of it.
+#[derive(Debug)]
+pub struct FeedArchiveWriter {
+    data: BufWriter<File>,
+    index: BufWriter<File>,
+    /// Index entries of records not flushed yet.
+    pending: Vec<IndexEntry>,
+    offset: u64,
+    last: Option<u64>,
+}
+
+impl FeedArchiveWriter {
+    /// Creates a new archive at `path`, replacing any existing one.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, FeedArchiveError> {
+        let path = path.as_ref();
+        let mut data = BufWriter::new(File::create(path)?);
+        data.write_all(&ARCHIVE_MAGIC)?;
+        Ok(Self {
+            data,
+            index: BufWriter::new(File::create(index_path(path))?),
+            pending: Vec::new(),
+            offset: ARCHIVE_MAGIC.len() as u64,
+            last: None,
+        })
+    }
+
+    /// Opens the archive at `path` for appending, creating it if it does not exist.
+    ///
+    /// A partial record left at the end of the data file by a crash is cut off.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FeedArchiveError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Self::create(path);
+        }
+        let mut data = OpenOptions::new().read(true).append(true).open(path)?;
+        let loaded = load_index(path, &mut data)?;
+        if loaded.end < data.metadata()?.len() {
+            data.set_len(loaded.end)?;
+        }
+        let mut index = if loaded.rebuilt {
+            let mut index = BufWriter::new(File::create(index_path(path))?);
+            for entry in &loaded.entries {
+                write_index_entry(&mut index, *entry)?;
+            }
+            index
+        } else {
+            BufWriter::new(OpenOptions::new().append(true).open(index_path(path))?)
+        };
+        index.flush()?;
+        Ok(Self {
+            data: BufWriter::new(data),
+            index,
+            pending: Vec::new(),
+            offset: loaded.end,
+            last: loaded.entries.last().map(|entry| entry.sequence_number),
+        })
+    }
+
+    /// Returns the last archived sequence number.
+    pub const fn last_sequence_number(&self) -> Option<u64> {
+        self.last
+    }
+
+    /// Appends `msg`, stamped with the current time.
+    pub fn append(&mut self, msg: &BroadcastFeedMessage) -> Result<(), FeedArchiveError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        self.append_at(msg, u64::try_from(now.as_millis()).unwrap_or(u64::MAX))
+    }
+
+    /// Appends `msg` with an explicit receive time in milliseconds since the Unix epoch.
+    pub fn append_at(
+        &mut self,
+        msg: &BroadcastFeedMessage,
+        received_at_ms: u64,
+    ) -> Result<(), FeedArchiveError> {
+        if let Some(last) = self.last
+            && msg.sequence_number <= last
+        {
+            return Err(FeedArchiveError::OutOfOrder {
+                last,
+                received: msg.sequence_number,
+            });
+        }
+        let payload = serde_json::to_vec(msg)?;
+        let len = u32::try_from(payload.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "feed message too large"))?;
+
+        self.data.write_all(&msg.sequence_number.to_be_bytes())?;
+        self.data.write_all(&received_at_ms.to_be_bytes())?;
+        self.data.write_all(&len.to_be_bytes())?;
+        self.data.write_all(&payload)?;
+        self.pending.push(IndexEntry {
+            sequence_number: msg.sequence_number,
+            offset: self.offset,
+        });
+        self.offset += RECORD_HEADER_LEN + u64::from(len);
+        self.last = Some(msg.sequence_number);
+        Ok(())
+    }
+
+    /// Flushes buffered records and index entries to disk.
+    pub fn flush(&mut self) -> Result<(), FeedArchiveError> {
+        self.data.flush()?;
+        for entry in self.pending.drain(..) {
+            write_index_entry(&mut self.index, entry)?;
+        }
+        self.index.flush()?;
+        Ok(())
+    }
+
+    /// Archives every message from a live feed until the stream ends, then flushes.
+    ///
+    /// Messages at or below the last archived sequence number are skipped.
+    pub async fn record<S>(&mut self, mut events: S) -> Result<(), FeedArchiveError>
+    where
+        S: Stream<Item = FeedEvent> + Unpin,
+    {
+        while let Some(event) = events.next().await {
+            if let FeedEvent::Message(msg) = event
+                && self.last.is_none_or(|last| msg.sequence_number > last)
+            {
+                self.append(&msg)?;
+            }
+        }
+        self.flush()
+    }
+}
+
+/// Reads an archive sequentially from any sequence number.
+#[derive(Debug)]
+pub struct FeedArchiveReader {
+    data: BufReader<File>,
+    index: Vec<IndexEntry>,
+    position: usize,
+}
+
+impl FeedArchiveReader {
+    /// Opens the archive at `path`, positioned at its first record.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FeedArchiveError> {
+        let path = path.as_ref();
+        let mut data = File::open(path)?;
+        let index = load_index(path, &mut data)?.entries;
+        Ok(Self {
+            data: BufReader::new(data),
+            index,
+            position: 0,
+        })
+    }
+
+    /// Returns the number of archived messages.
+    pub const fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the archive holds no messages.
+    pub const fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the first archived sequence number.
+    pub fn first_sequence_number(&self) -> Option<u64> {
+        self.index.first().map(|entry| entry.sequence_number)
+    }
+
+    /// Returns the last archived sequence number.
+    pub fn last_sequence_number(&self) -> Option<u64> {
+        self.index.last().map(|entry| entry.sequence_number)
+    }
+
+    /// Positions the reader at the first record with a sequence number of at least
+    /// `sequence_number`.
+    pub fn seek(&mut self, sequence_number: u64) -> Result<(), FeedArchiveError> {
+        let position = self
+            .index
+            .partition_point(|entry| entry.sequence_number < sequence_number);
+        if position == self.index.len() {
+            return Err(FeedArchiveError::NotFound(sequence_number));
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Reads the record at the current position and advances past it.
+    pub fn next_record(&mut self) -> Result<Option<ArchiveRecord>, FeedArchiveError> {
+        let Some(entry) = self.index.get(self.position).copied() else {
+            return Ok(None);
+        };
+        let truncated = |_| FeedArchiveError::Truncated {
+            offset: entry.offset,
+        };
+        self.data.seek(SeekFrom::Start(entry.offset))?;
+        let mut header = [0u8; RECORD_HEADER_LEN as usize];
+        self.data.read_exact(&mut header).map_err(truncated)?;
+        let received_at_ms = u64::from_be_bytes(header[8..16].try_into().unwrap());
+        let len = u32::from_be_bytes(header[16..].try_into().unwrap()) as usize;
+        let mut payload = alloc::vec![0u8; len];
+        self.data.read_exact(&mut payload).map_err(truncated)?;
+        self.position += 1;
+        Ok(Some(ArchiveRecord {
+            received_at_ms,
+            message: serde_json::from_slice(&payload)?,
+        }))
+    }
+}
+
+impl Iterator for FeedArchiveReader {
+    type Item = Result<ArchiveRecord, FeedArchiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Pacing of a [`FeedReplayer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Reproduce the recorded gaps between messages.
+    RealTime,
+    /// Divide the recorded gaps by this factor.
+    Accelerated(f64),
+    /// Emit messages without waiting.
+    AsFastAsPossible,
+}
+
+impl ReplaySpeed {
+    /// Returns how long to wait for a recorded gap of `recorded_ms` milliseconds.
+    pub fn delay(self, recorded_ms: u64) -> Duration {
+        let recorded = Duration::from_millis(recorded_ms);
+        match self {
+            Self::RealTime => recorded,
+            Self::Accelerated(factor) if factor > 0.0 && factor.is_finite() => {
+                recorded.div_f64(factor)
+            }
+            Self::Accelerated(_) | Self::AsFastAsPossible => Duration::ZERO,
+        }
+    }
+}
+
+/// Replays an archive as a [`FeedSubscription`], the same stream a live [`FeedClient`] yields.
+///
+/// [`FeedClient`]: crate::sequencer::client::FeedClient
+#[derive(Debug)]
+pub struct FeedReplayer {
+    reader: FeedArchiveReader,
+    speed: ReplaySpeed,
+    channel_capacity: usize,
+}
+
+impl FeedReplayer {
+    /// Creates a replayer starting at the reader's current position.
+    pub const fn new(reader: FeedArchiveReader, speed: ReplaySpeed) -> Self {
+        Self {
+            reader,
+            speed,
+            channel_capacity: 1024,
+        }
+    }
+
+    /// Opens the archive at `path` and positions it at `start`, or at its beginning for `None`.
+    pub fn open(
+        path: impl AsRef<Path>,
+        start: Option<u64>,
+        speed: ReplaySpeed,
+    ) -> Result<Self, FeedArchiveError> {
+        let mut reader = FeedArchiveReader::open(path)?;
+        if let Some(start) = start {
+            reader.seek(start)?;
+        }
+        Ok(Self::new(reader, speed))
+    }
+
+    /// Spawns the replay task and returns the event stream.
+    ///
+    /// Gaps in the archive are reported as [`FeedEvent::Gap`]. The stream ends after the last
+    /// record; a read error ends it with a [`FeedEvent::Disconnected`] without retry. Must be
+    /// called from within a Tokio runtime.
+    pub fn subscribe(self) -> FeedSubscription {
+        let (tx, rx) = mpsc::channel(self.channel_capacity);
+        let task = tokio::spawn(replay(self.reader, self.speed, tx));
+        FeedSubscription::new(rx, task)
+    }
+}
+
+async fn replay(mut reader: FeedArchiveReader, speed: ReplaySpeed, tx: mpsc::Sender<FeedEvent>) {
+    let mut tracker = SequenceTracker::new();
+    let mut previous: Option<u64> = None;
+    loop {
+        let record = match reader.next_record() {
+            Ok(Some(record)) => record,
+            Ok(None) => return,
+            Err(err) => {
+                let event = FeedEvent::Disconnected {
+                    reason: err.to_string(),
+                    retry_in: None,
+                };
+                let _ = tx.send(event).await;
+                return;
+            }
+        };
+        if let Some(previous) = previous {
+            let delay = speed.delay(record.received_at_ms.saturating_sub(previous));
+            if !delay.is_zero() {
+                sleep(delay).await;
+            }
+        }
+        previous = Some(record.received_at_ms);
+        for event in tracker.classify(record.message) {
+            if tx.send(event).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Instant,
+    };
+
+    use super::*;
+
+    /// Scratch directory removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "arb-feed-archive-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn msg(sequence_number: u64) -> BroadcastFeedMessage {
+        BroadcastFeedMessage {
+            sequence_number,
+            ..Default::default()
+        }
+    }
+
+    fn archive(dir: &TempDir, seqs: &[u64]) -> PathBuf {
+        let path = dir.0.join("feed.arb");
+        let mut writer = FeedArchiveWriter::create(&path).unwrap();
+        for (i, &seq) in seqs.iter().enumerate() {
+            writer.append_at(&msg(seq), 1_000 * i as u64).unwrap();
+        }
+        writer.flush().unwrap();
+        path
+    }
+
+    fn seqs(reader: FeedArchiveReader) -> Vec<u64> {
+        reader
+            .map(|record| record.unwrap().message.sequence_number)
+            .collect()
+    }
+
+    #[test]
+    fn archive_round_trips_and_seeks() {
+        let dir = TempDir::new();
+        let path = archive(&dir, &[3, 4, 5, 9]);
+        let mut reader = FeedArchiveReader::open(&path).unwrap();
+        assert_eq!(reader.len(), 4);
+        assert_eq!(reader.last_sequence_number(), Some(9));
+
+        reader.seek(5).unwrap();
+        let record = reader.next_record().unwrap().unwrap();
+        assert_eq!(record.message, msg(5));
+        assert_eq!(record.received_at_ms, 2_000);
+
+        reader.seek(6).unwrap();
+        assert_eq!(seqs(reader), [9]);
+        assert!(matches!(
+            FeedArchiveReader::open(&path).unwrap().seek(10),
+            Err(FeedArchiveError::NotFound(10))
+        ));
+    }
+
+    #[test]
+    fn writer_appends_and_rebuilds_missing_index() {
+        let dir = TempDir::new();
+        let path = archive(&dir, &[1, 2]);
+        let stale_index = std::fs::read(index_path(&path)).unwrap();
+        std::fs::remove_file(index_path(&path)).unwrap();
+
+        let mut writer = FeedArchiveWriter::open(&path).unwrap();
+        assert_eq!(writer.last_sequence_number(), Some(2));
+        assert!(matches!(
+            writer.append_at(&msg(2), 0),
+            Err(FeedArchiveError::OutOfOrder {
+                last: 2,
+                received: 2
+            })
+        ));
+        writer.append_at(&msg(3), 0).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(seqs(FeedArchiveReader::open(&path).unwrap()), [1, 2, 3]);
+
+        // An index missing the last record is rebuilt instead of trusted.
+        std::fs::write(index_path(&path), stale_index).unwrap();
+        let mut writer = FeedArchiveWriter::open(&path).unwrap();
+        assert_eq!(writer.last_sequence_number(), Some(3));
+        writer.append_at(&msg(4), 0).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(seqs(FeedArchiveReader::open(&path).unwrap()), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn writer_drops_partial_trailing_record() {
+        let dir = TempDir::new();
+        let path = archive(&dir, &[1, 2]);
+        let complete = std::fs::metadata(&path).unwrap().len();
+
+        // A crash mid-write leaves a record header promising more payload than was written.
+        let mut data = OpenOptions::new().append(true).open(&path).unwrap();
+        data.write_all(&3u64.to_be_bytes()).unwrap();
+        data.write_all(&0u64.to_be_bytes()).unwrap();
+        data.write_all(&100u32.to_be_bytes()).unwrap();
+        data.write_all(b"{\"ver").unwrap();
+        drop(data);
+
+        assert_eq!(seqs(FeedArchiveReader::open(&path).unwrap()), [1, 2]);
+
+        let mut writer = FeedArchiveWriter::open(&path).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), complete);
+        assert_eq!(writer.last_sequence_number(), Some(2));
+        writer.append_at(&msg(3), 0).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(seqs(FeedArchiveReader::open(&path).unwrap()), [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn replayer_streams_events_with_pacing() {
+        let dir = TempDir::new();
+        let path = archive(&dir, &[1, 2, 4]);
+
+        let events: Vec<FeedEvent> = FeedReplayer::open(&path, None, ReplaySpeed::AsFastAsPossible)
+            .unwrap()
+            .subscribe()
+            .collect()
+            .await;
+        assert_eq!(
+            events,
+            [
+                FeedEvent::Message(msg(1)),
+                FeedEvent::Message(msg(2)),
+                FeedEvent::Gap {
+                    expected: 3,
+                    received: 4
+                },
+                FeedEvent::Message(msg(4)),
+            ]
+        );
+
+        let started = Instant::now();
+        let seqs: Vec<u64> = FeedReplayer::open(&path, Some(2), ReplaySpeed::Accelerated(20.0))
+            .unwrap()
+            .subscribe()
+            .into_messages()
+            .map(|msg| msg.sequence_number)
+            .collect()
+            .await;
+        assert_eq!(seqs, [2, 4]);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,683 @@
+//! WebSocket client for the Nitro sequencer feed.
+//!
+//! Nitro reference: `broadcastclient/broadcastclient.go`.
+
+use alloc::{boxed::Box, string::String, string::ToString};
+use core::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{sleep, timeout},
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{self, Message, client::IntoClientRequest, http::HeaderValue},
+};
+
+use alloy_primitives::Address;
+
+use crate::sequencer::{
+    event::{FeedEvent, SequenceTracker},
+    feed::{BroadcastFeedMessage, Root},
+    signature::FeedSignatureError,
+};
+
+/// Feed protocol version announced by this client.
+pub const FEED_CLIENT_VERSION: u64 = 2;
+/// Header carrying the feed client protocol version.
+pub const HEADER_FEED_CLIENT_VERSION: &str = "Arbitrum-Feed-Client-Version";
+/// Header carrying the sequence number the client wants to resume from.
+pub const HEADER_REQUESTED_SEQUENCE_NUMBER: &str = "Arbitrum-Requested-Sequence-Number";
+/// Header carrying the chain id the client expects.
+pub const HEADER_CHAIN_ID: &str = "Arbitrum-Chain-Id";
+
+/// Connection and reconnect settings for [`FeedClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedClientConfig {
+    /// Feed URL (`ws://` or `wss://`).
+    pub url: String,
+    /// Sequence number to request on the first connection.
+    pub start_sequence_number: Option<u64>,
+    /// Chain id sent in the `Arbitrum-Chain-Id` header when set.
+    pub chain_id: Option<u64>,
+    /// Sequencer key every message must be signed by, or `None` to accept unsigned feeds.
+    ///
+    /// Verification needs [`Self::chain_id`] to be set.
+    pub expected_signer: Option<Address>,
+    /// Delay before the first reconnect attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound for the exponential reconnect delay.
+    pub max_backoff: Duration,
+    /// Number of consecutive failed reconnects before giving up, or `None`
+    /// to retry forever.
+    pub max_reconnect_attempts: Option<u32>,
+    /// Maximum time without any frame before the connection is considered dead.
+    pub idle_timeout: Duration,
+    /// Capacity of the event channel between the connection task and the stream.
+    pub channel_capacity: usize,
+}
+
+impl FeedClientConfig {
+    /// Creates a config for `url` with Nitro-like defaults.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            start_sequence_number: None,
+            chain_id: None,
+            expected_signer: None,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(30),
+            max_reconnect_attempts: None,
+            idle_timeout: Duration::from_secs(20),
+            channel_capacity: 1024,
+        }
+    }
+
+    /// Sets the sequence number requested on the first connection.
+    pub const fn with_start_sequence_number(mut self, sequence_number: u64) -> Self {
+        self.start_sequence_number = Some(sequence_number);
+        self
+    }
+
+    /// Sets the chain id announced to the feed server.
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Requires every message to be signed by `signer`.
+    ///
+    /// A message with a missing or foreign signature drops the connection, which is then
+    /// resumed from the last accepted sequence number.
+    pub const fn with_expected_signer(mut self, signer: Address) -> Self {
+        self.expected_signer = Some(signer);
+        self
+    }
+
+    /// Sets the reconnect backoff bounds.
+    pub const fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Limits the number of consecutive failed reconnect attempts.
+    pub const fn with_max_reconnect_attempts(mut self, attempts: u32) -> Self {
+        self.max_reconnect_attempts = Some(attempts);
+        self
+    }
+
+    /// Sets the idle timeout after which a silent connection is dropped.
+    pub const fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Checks that the settings can be used to subscribe.
+    pub const fn validate(&self) -> Result<(), FeedClientError> {
+        if self.expected_signer.is_some() && self.chain_id.is_none() {
+            return Err(FeedClientError::MissingChainId);
+        }
+        Ok(())
+    }
+
+    /// Returns the reconnect delay for the given 1-based attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Error raised by a single feed connection.
+///
+/// These errors are reported through [`FeedEvent::Disconnected`] before reconnecting, except
+/// for [terminal](Self::is_terminal) ones, which end the subscription.
+#[derive(Debug)]
+pub enum FeedClientError {
+    /// The handshake request could not be built from the config.
+    InvalidRequest(String),
+    /// WebSocket transport or handshake failure.
+    WebSocket(Box<tungstenite::Error>),
+    /// A frame could not be decoded into a feed [`Root`].
+    Decode(serde_json::Error),
+    /// No frame arrived within the configured idle timeout.
+    IdleTimeout(Duration),
+    /// The server closed the connection.
+    Closed,
+    /// Signature verification was requested without a configured chain id.
+    MissingChainId,
+    /// A message failed sequencer signature verification.
+    Signature {
+        /// Sequence number of the rejected message.
+        sequence_number: u64,
+        /// Verification failure.
+        err: FeedSignatureError,
+    },
+}
+
+impl fmt::Display for FeedClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRequest(msg) => write!(f, "invalid feed request: {msg}"),
+            Self::WebSocket(err) => write!(f, "feed websocket error: {err}"),
+            Self::Decode(err) => write!(f, "failed to decode feed frame: {err}"),
+            Self::IdleTimeout(after) => write!(f, "no feed frame received for {after:?}"),
+            Self::Closed => write!(f, "feed connection closed by server"),
+            Self::MissingChainId => {
+                write!(f, "signature verification requires a chain id")
+            }
+            Self::Signature {
+                sequence_number,
+                err,
+            } => write!(f, "rejected feed message {sequence_number}: {err}"),
+        }
+    }
+}
+
+impl FeedClientError {
+    /// Returns `true` if reconnecting cannot fix the error.
+    ///
+    /// A message failing signature verification would be replayed by the same feed on every
+    /// reconnect.
+    pub const fn is_terminal(&self) -> bool {
+        matches!(self, Self::Signature { .. })
+    }
+}
+
+impl core::error::Error for FeedClientError {}
+
+impl From<tungstenite::Error> for FeedClientError {
+    fn from(err: tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(err))
+    }
+}
+
+/// Reconnecting client for a Nitro sequencer feed.
+#[derive(Debug, Clone)]
+pub struct FeedClient {
+    config: FeedClientConfig,
+}
+
+impl FeedClient {
+    /// Creates a client for `url` with default settings.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::from_config(FeedClientConfig::new(url))
+    }
+
+    /// Creates a client from an explicit config.
+    pub const fn from_config(config: FeedClientConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the client config.
+    pub const fn config(&self) -> &FeedClientConfig {
+        &self.config
+    }
+
+    /// Checks that `msg` was signed by `expected_signer` for the configured chain id.
+    pub fn verify_signature(
+        &self,
+        msg: &BroadcastFeedMessage,
+        expected_signer: Address,
+    ) -> Result<(), FeedClientError> {
+        verify_signature(&self.config, msg, expected_signer)
+    }
+
+    /// Spawns the connection task and returns the event stream.
+    ///
+    /// Fails if the config does not [validate](FeedClientConfig::validate). Must be called
+    /// from within a Tokio runtime. Dropping the returned [`FeedSubscription`] stops the
+    /// connection task.
+    pub fn subscribe(self) -> Result<FeedSubscription, FeedClientError> {
+        self.config.validate()?;
+        let (tx, rx) = mpsc::channel(self.config.channel_capacity.max(1));
+        let task = tokio::spawn(run(self.config, tx));
+        Ok(FeedSubscription::new(rx, task))
+    }
+}
+
+/// Stream of [`FeedEvent`]s produced by a [`FeedClient`].
+///
+/// The stream ends after a [`FeedEvent::Disconnected`] with `retry_in: None`.
+#[derive(Debug)]
+pub struct FeedSubscription {
+    rx: mpsc::Receiver<FeedEvent>,
+    task: JoinHandle<()>,
+}
+
+impl FeedSubscription {
+    /// Wraps the receiving end of a task that produces feed events.
+    pub(crate) const fn new(rx: mpsc::Receiver<FeedEvent>, task: JoinHandle<()>) -> Self {
+        Self { rx, task }
+    }
+
+    /// Adapts the subscription into a stream of in-order feed messages,
+    /// discarding gap, duplicate, confirmation and disconnect notifications.
+    pub fn into_messages(self) -> impl Stream<Item = BroadcastFeedMessage> + Send + Unpin {
+        self.filter_map(|event| core::future::ready(event.into_message()))
+    }
+}
+
+impl Stream for FeedSubscription {
+    type Item = FeedEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for FeedSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Connection loop: connect, forward frames, back off and resume on failure.
+async fn run(config: FeedClientConfig, tx: mpsc::Sender<FeedEvent>) {
+    let mut tracker = config
+        .start_sequence_number
+        .map_or_else(SequenceTracker::new, SequenceTracker::starting_at);
+    let mut attempt = 0u32;
+
+    loop {
+        let err = match connect(&config, tracker.next_expected()).await {
+            Ok(ws) => match forward(ws, &config, &mut tracker, &mut attempt, &tx).await {
+                Ok(()) => return,
+                Err(err) => err,
+            },
+            Err(err) => err,
+        };
+
+        attempt = attempt.saturating_add(1);
+        let retry_in = match config.max_reconnect_attempts {
+            _ if err.is_terminal() => None,
+            Some(max) if attempt > max => None,
+            _ => Some(config.backoff(attempt)),
+        };
+        let event = FeedEvent::Disconnected {
+            reason: err.to_string(),
+            retry_in,
+        };
+        if tx.send(event).await.is_err() {
+            return;
+        }
+        let Some(delay) = retry_in else {
+            return;
+        };
+        sleep(delay).await;
+    }
+}
+
+type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+async fn connect(
+    config: &FeedClientConfig,
+    requested_sequence_number: Option<u64>,
+) -> Result<WsStream, FeedClientError> {
+    let mut request = config
+        .url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| FeedClientError::InvalidRequest(e.to_string()))?;
+    let headers = request.headers_mut();
+    headers.insert(
+        HEADER_FEED_CLIENT_VERSION,
+        HeaderValue::from(FEED_CLIENT_VERSION),
+    );
+    if let Some(seq) = requested_sequence_number {
+        headers.insert(HEADER_REQUESTED_SEQUENCE_NUMBER, HeaderValue::from(seq));
+    }
+    if let Some(chain_id) = config.chain_id {
+        headers.insert(HEADER_CHAIN_ID, HeaderValue::from(chain_id));
+    }
+
+    let (ws, _response) = connect_async(request).await?;
+    Ok(ws)
+}
+
+/// Forwards frames until the connection fails. Returns `Ok(())` once the
+/// subscriber is gone.
+///
+/// The reconnect `attempt` counter is reset once a new message is delivered, so a feed that
+/// fails right after connecting, or only replays old messages, still backs off.
+async fn forward(
+    mut ws: WsStream,
+    config: &FeedClientConfig,
+    tracker: &mut SequenceTracker,
+    attempt: &mut u32,
+    tx: &mpsc::Sender<FeedEvent>,
+) -> Result<(), FeedClientError> {
+    loop {
+        let frame = match timeout(config.idle_timeout, ws.next()).await {
+            Ok(Some(frame)) => frame?,
+            Ok(None) => return Err(FeedClientError::Closed),
+            Err(_) => return Err(FeedClientError::IdleTimeout(config.idle_timeout)),
+        };
+        let root: Root = match &frame {
+            Message::Text(text) => {
+                serde_json::from_str(text.as_str()).map_err(FeedClientError::Decode)?
+            }
+            Message::Binary(data) => {
+                serde_json::from_slice(data).map_err(FeedClientError::Decode)?
+            }
+            Message::Close(_) => return Err(FeedClientError::Closed),
+            _ => continue,
+        };
+
+        for msg in root.messages.unwrap_or_default() {
+            if let Some(signer) = config.expected_signer {
+                verify_signature(config, &msg, signer)?;
+            }
+            for event in tracker.classify(msg) {
+                if matches!(event, FeedEvent::Message(_)) {
+                    *attempt = 0;
+                }
+                if tx.send(event).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        if let Some(confirmed) = root.confirmed_sequence_number_message {
+            let event = FeedEvent::ConfirmedSequenceNumber {
+                sequence_number: confirmed.sequence_number,
+            };
+            if tx.send(event).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn verify_signature(
+    config: &FeedClientConfig,
+    msg: &BroadcastFeedMessage,
+    expected_signer: Address,
+) -> Result<(), FeedClientError> {
+    let chain_id = config.chain_id.ok_or(FeedClientError::MissingChainId)?;
+    msg.verify_signature(chain_id, expected_signer)
+        .map_err(|err| FeedClientError::Signature {
+            sequence_number: msg.sequence_number,
+            err,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{
+        accept_hdr_async,
+        tungstenite::handshake::server::{Request, Response},
+    };
+
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    use super::*;
+    use crate::sequencer::feed::{BroadcastFeedMessage, ConfirmedSequenceNumberMessage, Root};
+
+    fn frame(seqs: &[u64]) -> Message {
+        let root = Root {
+            version: 1,
+            messages: Some(
+                seqs.iter()
+                    .map(|&sequence_number| BroadcastFeedMessage {
+                        sequence_number,
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            confirmed_sequence_number_message: None,
+        };
+        Message::text(serde_json::to_string(&root).unwrap())
+    }
+
+    fn confirmed(sequence_number: u64) -> Message {
+        let root = Root {
+            version: 1,
+            messages: None,
+            confirmed_sequence_number_message: Some(ConfirmedSequenceNumberMessage {
+                sequence_number,
+            }),
+        };
+        Message::text(serde_json::to_string(&root).unwrap())
+    }
+
+    /// Serves one scripted connection per entry in `sessions`, recording the
+    /// requested sequence number header of each handshake.
+    async fn stand_in(sessions: Vec<Vec<Message>>) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let seen = requested.clone();
+        tokio::spawn(async move {
+            for frames in sessions {
+                let (stream, _) = listener.accept().await.unwrap();
+                let seen = seen.clone();
+                let mut ws = accept_hdr_async(stream, move |req: &Request, resp: Response| {
+                    assert_eq!(
+                        req.headers()
+                            .get(HEADER_FEED_CLIENT_VERSION)
+                            .and_then(|v| v.to_str().ok()),
+                        Some("2")
+                    );
+                    seen.lock().unwrap().push(
+                        req.headers()
+                            .get(HEADER_REQUESTED_SEQUENCE_NUMBER)
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_owned),
+                    );
+                    Ok(resp)
+                })
+                .await
+                .unwrap();
+                for frame in frames {
+                    ws.send(frame).await.unwrap();
+                }
+                ws.close(None).await.ok();
+            }
+        });
+        (url, requested)
+    }
+
+    /// Collects the `retry_in` of every disconnect until the subscription ends.
+    async fn retry_delays(config: FeedClientConfig) -> Vec<Option<Duration>> {
+        FeedClient::from_config(config)
+            .subscribe()
+            .unwrap()
+            .filter_map(|event| async move {
+                match event {
+                    FeedEvent::Disconnected { retry_in, .. } => Some(retry_in),
+                    _ => None,
+                }
+            })
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn client_reports_gaps_drops_duplicates_and_resumes() {
+        let (url, requested) = stand_in(vec![
+            vec![frame(&[5, 6]), confirmed(5), frame(&[6, 8])],
+            vec![frame(&[9, 10])],
+        ])
+        .await;
+
+        let config = FeedClientConfig::new(url)
+            .with_start_sequence_number(5)
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(10))
+            .with_max_reconnect_attempts(1);
+        let events: Vec<FeedEvent> = FeedClient::from_config(config)
+            .subscribe()
+            .unwrap()
+            .collect()
+            .await;
+
+        let summary: Vec<String> = events
+            .iter()
+            .map(|event| match event {
+                FeedEvent::Message(msg) => format!("msg {}", msg.sequence_number),
+                FeedEvent::Gap { expected, received } => format!("gap {expected}->{received}"),
+                FeedEvent::Duplicate { sequence_number } => format!("dup {sequence_number}"),
+                FeedEvent::ConfirmedSequenceNumber { sequence_number } => {
+                    format!("confirmed {sequence_number}")
+                }
+                FeedEvent::Disconnected { retry_in, .. } => {
+                    format!("disconnected retry={}", retry_in.is_some())
+                }
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                "msg 5",
+                "msg 6",
+                "confirmed 5",
+                "dup 6",
+                "gap 7->8",
+                "msg 8",
+                "disconnected retry=true",
+                "msg 9",
+                "msg 10",
+                "disconnected retry=true",
+                "disconnected retry=false",
+            ]
+        );
+        assert_eq!(
+            *requested.lock().unwrap(),
+            [Some("5".to_owned()), Some("9".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn into_messages_yields_only_messages() {
+        let (url, _) = stand_in(vec![vec![frame(&[1, 3])]]).await;
+        let config = FeedClientConfig::new(url).with_max_reconnect_attempts(0);
+        let seqs: Vec<u64> = FeedClient::from_config(config)
+            .subscribe()
+            .unwrap()
+            .into_messages()
+            .map(|msg| msg.sequence_number)
+            .collect()
+            .await;
+        assert_eq!(seqs, [1, 3]);
+    }
+
+    #[tokio::test]
+    async fn client_drops_connection_on_unsigned_message() {
+        let signer = PrivateKeySigner::random();
+        let mut signed = BroadcastFeedMessage {
+            sequence_number: 1,
+            ..Default::default()
+        };
+        signed
+            .message_with_meta_data
+            .l1_incoming_message
+            .header
+            .sender = Address::ZERO.to_string();
+        let hash = signed.hash(42161).unwrap();
+        let signature = signer.sign_hash_sync(&hash).unwrap();
+        signed.signature = Some(signature.as_bytes().into());
+        let mut unsigned = signed.clone();
+        unsigned.sequence_number = 2;
+        unsigned.signature = None;
+
+        let root = Root {
+            version: 1,
+            messages: Some(vec![signed, unsigned]),
+            confirmed_sequence_number_message: None,
+        };
+        let (url, _) = stand_in(vec![vec![Message::text(
+            serde_json::to_string(&root).unwrap(),
+        )]])
+        .await;
+        let config = FeedClientConfig::new(url)
+            .with_chain_id(42161)
+            .with_expected_signer(signer.address());
+        let events: Vec<FeedEvent> = FeedClient::from_config(config)
+            .subscribe()
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_message().map(|msg| msg.sequence_number),
+            Some(1)
+        );
+        assert!(matches!(
+            &events[1],
+            FeedEvent::Disconnected { reason, retry_in: None }
+                if reason == "rejected feed message 2: feed message is not signed"
+        ));
+    }
+
+    #[tokio::test]
+    async fn failures_after_connecting_still_count_as_attempts() {
+        let garbage = || vec![Message::text("not a feed frame")];
+        let (url, requested) = stand_in(vec![garbage(), garbage(), garbage()]).await;
+        let config = FeedClientConfig::new(url)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(10))
+            .with_max_reconnect_attempts(2);
+        assert_eq!(
+            retry_delays(config).await,
+            [
+                Some(Duration::from_millis(1)),
+                Some(Duration::from_millis(2)),
+                None
+            ]
+        );
+        assert_eq!(requested.lock().unwrap().len(), 3);
+
+        // Replayed messages do not count as progress.
+        let (url, _) = stand_in(vec![
+            vec![frame(&[5])],
+            vec![frame(&[5])],
+            vec![frame(&[5])],
+        ])
+        .await;
+        let config = FeedClientConfig::new(url)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(10))
+            .with_max_reconnect_attempts(2);
+        assert_eq!(
+            retry_delays(config).await,
+            [
+                Some(Duration::from_millis(1)),
+                Some(Duration::from_millis(2)),
+                None
+            ]
+        );
+
+        let config = FeedClientConfig::new("ws://127.0.0.1:1").with_expected_signer(Address::ZERO);
+        assert!(matches!(
+            FeedClient::from_config(config).subscribe(),
+            Err(FeedClientError::MissingChainId)
+        ));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_and_is_capped() {
+        let config = FeedClientConfig::new("ws://localhost")
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(4), Duration::from_millis(800));
+        assert_eq!(config.backoff(5), Duration::from_secs(1));
+        assert_eq!(config.backoff(200), Duration::from_secs(1));
+    }
+}
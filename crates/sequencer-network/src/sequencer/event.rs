@@ -0,0 +1,153 @@
+use alloc::string::String;
+use core::time::Duration;
+
+use crate::sequencer::feed::BroadcastFeedMessage;
+
+/// Event yielded by sequencer feed streams.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum FeedEvent {
+    /// A new feed message, delivered in sequence order.
+    Message(BroadcastFeedMessage),
+    /// The feed skipped one or more sequence numbers.
+    ///
+    /// Emitted right before the message carrying `received`.
+    Gap {
+        /// Sequence number that was expected next.
+        expected: u64,
+        /// Sequence number that was actually received.
+        received: u64,
+    },
+    /// A message that was already delivered was received again and dropped.
+    Duplicate {
+        /// Sequence number of the dropped message.
+        sequence_number: u64,
+    },
+    /// The feed confirmed that every message up to `sequence_number` is posted to L1.
+    ConfirmedSequenceNumber {
+        /// Highest confirmed sequence number.
+        sequence_number: u64,
+    },
+    /// The underlying connection was lost.
+    Disconnected {
+        /// Human-readable reason for the disconnect.
+        reason: String,
+        /// Delay before the next reconnect attempt, or `None` when the stream
+        /// gives up and terminates.
+        retry_in: Option<Duration>,
+    },
+}
+
+impl FeedEvent {
+    /// Returns the feed message if this event carries one.
+    pub const fn as_message(&self) -> Option<&BroadcastFeedMessage> {
+        match self {
+            Self::Message(msg) => Some(msg),
+            _ => None,
+        }
+    }
+
+    /// Consumes the event and returns the feed message if it carries one.
+    pub fn into_message(self) -> Option<BroadcastFeedMessage> {
+        match self {
+            Self::Message(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
+
+/// Classification of an observed sequence number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceStatus {
+    /// The sequence number is the next expected one (or the first one seen).
+    InOrder,
+    /// The sequence number was already observed.
+    Duplicate,
+    /// The sequence number is ahead of the expected one.
+    Gap {
+        /// Sequence number that was expected next.
+        expected: u64,
+    },
+}
+
+/// Tracks feed sequence numbers to detect gaps and duplicates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SequenceTracker {
+    next: Option<u64>,
+}
+
+impl SequenceTracker {
+    /// Creates a tracker that accepts any first sequence number.
+    pub const fn new() -> Self {
+        Self { next: None }
+    }
+
+    /// Creates a tracker that expects `next` as the first sequence number.
+    pub const fn starting_at(next: u64) -> Self {
+        Self { next: Some(next) }
+    }
+
+    /// Returns the next expected sequence number, if any message was observed
+    /// or a starting point was configured.
+    pub const fn next_expected(&self) -> Option<u64> {
+        self.next
+    }
+
+    /// Records `sequence_number` and classifies it against the expected one.
+    ///
+    /// Gaps advance the tracker past the received number; duplicates leave it
+    /// untouched.
+    pub const fn observe(&mut self, sequence_number: u64) -> SequenceStatus {
+        let status = match self.next {
+            None => SequenceStatus::InOrder,
+            Some(next) if sequence_number == next => SequenceStatus::InOrder,
+            Some(next) if sequence_number < next => return SequenceStatus::Duplicate,
+            Some(expected) => SequenceStatus::Gap { expected },
+        };
+        self.next = Some(sequence_number.saturating_add(1));
+        status
+    }
+
+    /// Observes `msg` and returns the events it produces: the message itself, a duplicate
+    /// notice instead of it, or a gap notice followed by it.
+    pub fn classify(&mut self, msg: BroadcastFeedMessage) -> impl Iterator<Item = FeedEvent> {
+        let received = msg.sequence_number;
+        let (first, second) = match self.observe(received) {
+            SequenceStatus::InOrder => (Some(FeedEvent::Message(msg)), None),
+            SequenceStatus::Duplicate => (
+                Some(FeedEvent::Duplicate {
+                    sequence_number: received,
+                }),
+                None,
+            ),
+            SequenceStatus::Gap { expected } => (
+                Some(FeedEvent::Gap { expected, received }),
+                Some(FeedEvent::Message(msg)),
+            ),
+        };
+        first.into_iter().chain(second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_classifies_in_order_gap_and_duplicate() {
+        let mut tracker = SequenceTracker::new();
+        assert_eq!(tracker.observe(10), SequenceStatus::InOrder);
+        assert_eq!(tracker.observe(11), SequenceStatus::InOrder);
+        assert_eq!(tracker.observe(11), SequenceStatus::Duplicate);
+        assert_eq!(tracker.observe(5), SequenceStatus::Duplicate);
+        assert_eq!(tracker.observe(14), SequenceStatus::Gap { expected: 12 });
+        assert_eq!(tracker.next_expected(), Some(15));
+    }
+
+    #[test]
+    fn tracker_with_start_reports_initial_gap() {
+        let mut tracker = SequenceTracker::starting_at(3);
+        assert_eq!(tracker.observe(4), SequenceStatus::Gap { expected: 3 });
+        assert_eq!(tracker.observe(2), SequenceStatus::Duplicate);
+    }
+}
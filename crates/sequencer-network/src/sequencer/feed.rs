@@ -1,11 +1,140 @@
-use alloc::{format, string::String, vec::Vec};
-use core::str::FromStr;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
 
-use alloy_core::hex::FromHex;
-use alloy_primitives::{Address, FixedBytes, U256};
+use alloy_core::hex;
+use alloy_primitives::{Address, B256, Bytes, FixedBytes, U256};
+use base64::{Engine, engine::general_purpose::STANDARD};
 use serde::*;
 use serde_json::Value;
 
+/// Error while normalizing a sequencer feed message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeedDecodeError {
+    /// The header `sender` is not a valid address.
+    InvalidPoster(String),
+    /// The header `requestId` is not null, a hex string or an unsigned integer.
+    InvalidRequestId(String),
+    /// The header `baseFeeL1` is not null, a hex string or an unsigned integer.
+    InvalidBaseFee(String),
+    /// The `l2Msg` payload is neither base64 nor `0x`-prefixed hex.
+    InvalidL2Msg(String),
+    /// The block metadata is empty.
+    EmptyBlockMetadata,
+    /// The block metadata starts with an unknown version byte.
+    UnknownBlockMetadataVersion(u8),
+}
+
+impl fmt::Display for FeedDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPoster(sender) => write!(f, "invalid poster address {sender}"),
+            Self::InvalidRequestId(value) => write!(f, "invalid request id {value}"),
+            Self::InvalidBaseFee(value) => write!(f, "invalid L1 base fee {value}"),
+            Self::InvalidL2Msg(err) => write!(f, "invalid l2Msg payload: {err}"),
+            Self::EmptyBlockMetadata => write!(f, "block metadata is empty"),
+            Self::UnknownBlockMetadataVersion(version) => {
+                write!(f, "unknown block metadata version {version}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FeedDecodeError {}
+
+/// Parses a header field Nitro marshals as `*big.Int` or `*common.Hash`.
+///
+/// Accepts `null`, bare decimal numbers and strings holding `0x`-prefixed hex or decimal
+/// digits. Numbers wider than 64 bits arrive as decimal strings, see [`lossless`].
+fn value_to_u256(value: &Value) -> Option<Option<U256>> {
+    let text = match value {
+        Value::Null => return Some(None),
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    let (digits, radix) = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .map_or((text.as_str(), 10), |hex| (hex, 16));
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    U256::from_str_radix(digits, radix as u64).ok().map(Some)
+}
+
+/// Serde adapter for header fields that may hold integers wider than 64 bits.
+///
+/// `serde_json` rounds such numbers to `f64`, so the field is read as raw JSON first and a bare
+/// integer that does not fit in a `u64` is kept as a string of its decimal digits. Such strings
+/// are written back as bare numbers.
+mod lossless {
+    use alloc::{borrow::ToOwned, boxed::Box};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+    use serde_json::{Value, value::RawValue};
+
+    /// Returns `true` if `text` is an integer literal too wide for a `u64`.
+    fn is_wide_integer(text: &str) -> bool {
+        !text.is_empty()
+            && text.bytes().all(|byte| byte.is_ascii_digit())
+            && text.parse::<u64>().is_err()
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Value,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Value::String(text) if is_wide_integer(text) => RawValue::from_string(text.clone())
+                .map_err(ser::Error::custom)?
+                .serialize(serializer),
+            _ => value.serialize(serializer),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        let text = raw.get();
+        if is_wide_integer(text) {
+            return Ok(Value::String(text.to_owned()));
+        }
+        serde_json::from_str(text).map_err(de::Error::custom)
+    }
+}
+
+/// Serde adapter for optional byte fields that Nitro marshals as base64 or `null`.
+mod base64_opt {
+    use alloc::string::String;
+
+    use alloy_primitives::Bytes;
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => serializer.serialize_str(&STANDARD.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| STANDARD.decode(text).map(Bytes::from))
+            .transpose()
+            .map_err(de::Error::custom)
+    }
+}
+
 /// Root JSON object for a sequencer feed payload batch.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,6 +143,17 @@ pub struct Root {
     pub version: u8,
     /// Optional list of broadcast feed messages.
     pub messages: Option<Vec<BroadcastFeedMessage>>,
+    /// Highest sequence number confirmed on L1, sent in its own frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmed_sequence_number_message: Option<ConfirmedSequenceNumberMessage>,
+}
+
+/// Notification that every message up to `sequence_number` is confirmed on L1.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmedSequenceNumberMessage {
+    /// Highest confirmed sequence number.
+    pub sequence_number: u64,
 }
 
 /// Single sequencer feed message entry with ordering metadata.
@@ -25,6 +165,74 @@ pub struct BroadcastFeedMessage {
     /// Message body and message-level metadata.
     #[serde(rename = "message")]
     pub message_with_meta_data: MessageWithMetadata,
+    /// Hash of the L2 block the sequencer produced from this message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<B256>,
+    /// Sequencer signature over the message hash, base64-encoded; `null` on unsigned feeds.
+    #[serde(default, with = "base64_opt")]
+    pub signature: Option<Bytes>,
+    /// Per-block metadata, such as which transactions were timeboosted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_metadata: Option<BlockMetadata>,
+}
+
+/// Block metadata attached to feed messages: a version byte followed by a bitmap with one bit
+/// per transaction, set when the transaction was timeboosted.
+///
+/// Nitro reference: `go-ethereum/common/types.go` -> `BlockMetadata.IsTxTimeboosted`.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct BlockMetadata(pub Bytes);
+
+impl BlockMetadata {
+    /// Returns the metadata version byte.
+    pub fn version(&self) -> Result<u8, FeedDecodeError> {
+        self.0
+            .first()
+            .copied()
+            .ok_or(FeedDecodeError::EmptyBlockMetadata)
+    }
+
+    /// Returns the timeboost bitmap, checking that the metadata uses version 0.
+    fn bitmap(&self) -> Result<&[u8], FeedDecodeError> {
+        match self.version()? {
+            0 => Ok(&self.0[1..]),
+            version => Err(FeedDecodeError::UnknownBlockMetadataVersion(version)),
+        }
+    }
+
+    /// Returns whether the transaction at `tx_index` in the block was timeboosted.
+    ///
+    /// Indices past the end of the bitmap are not timeboosted.
+    pub fn is_tx_timeboosted(&self, tx_index: usize) -> Result<bool, FeedDecodeError> {
+        let bitmap = self.bitmap()?;
+        Ok(bitmap
+            .get(tx_index / 8)
+            .is_some_and(|byte| byte & (1 << (tx_index % 8)) != 0))
+    }
+
+    /// Returns the indices of all timeboosted transactions in the block.
+    pub fn timeboosted_txs(&self) -> Result<Vec<usize>, FeedDecodeError> {
+        let bitmap = self.bitmap()?;
+        Ok((0..bitmap.len() * 8)
+            .filter(|index| bitmap[index / 8] & (1 << (index % 8)) != 0)
+            .collect())
+    }
+}
+
+impl Serialize for BlockMetadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockMetadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        STANDARD
+            .decode(text)
+            .map(|bytes| Self(bytes.into()))
+            .map_err(de::Error::custom)
+    }
 }
 
 /// Message payload with delayed inbox progress metadata.
@@ -55,7 +263,7 @@ pub struct BatchDataStats {
 pub struct L1IncomingMessage {
     /// L1 message header.
     pub header: Header,
-    /// Hex-encoded L2 payload bytes.
+    /// L2 payload bytes, base64-encoded as Nitro marshals `[]byte` fields.
     #[serde(rename = "l2Msg")]
     pub l2msg: String,
     /// Legacy gas accounting for batch posting report messages.
@@ -66,6 +274,34 @@ pub struct L1IncomingMessage {
     pub batch_data_stats: Option<BatchDataStats>,
 }
 
+impl L1IncomingMessage {
+    /// Creates a message carrying `l2msg`, without batch posting report metadata.
+    pub fn new(header: Header, l2msg: &[u8]) -> Self {
+        Self {
+            header,
+            l2msg: STANDARD.encode(l2msg),
+            legacy_batch_gas_cost: None,
+            batch_data_stats: None,
+        }
+    }
+
+    /// Decodes the `l2Msg` payload into raw bytes.
+    ///
+    /// Nitro feeds carry base64; `0x`-prefixed hex is accepted for hand-written fixtures.
+    pub fn l2msg_bytes(&self) -> Result<Vec<u8>, FeedDecodeError> {
+        self.l2msg.strip_prefix("0x").map_or_else(
+            || {
+                STANDARD
+                    .decode(&self.l2msg)
+                    .map_err(|e| FeedDecodeError::InvalidL2Msg(e.to_string()))
+            },
+            |hex_str| {
+                hex::decode(hex_str).map_err(|e| FeedDecodeError::InvalidL2Msg(e.to_string()))
+            },
+        )
+    }
+}
+
 /// Raw L1 inbox message header as provided by feed JSON.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -78,12 +314,39 @@ pub struct Header {
     pub block_number: u64,
     /// L1 timestamp for this message.
     pub timestamp: u64,
-    /// Request identifier (shape varies by message type).
+    /// Request identifier: `null` or a 32-byte hex string.
+    #[serde(with = "lossless")]
     pub request_id: Value,
-    /// L1 base fee (shape varies by message type).
+    /// L1 base fee: `null` or a decimal number of arbitrary size.
+    #[serde(with = "lossless")]
     pub base_fee_l1: Value,
 }
 
+impl Header {
+    /// Creates a header encoded the way Nitro marshals `L1IncomingMessageHeader`.
+    ///
+    /// A base fee wider than 64 bits is kept as a string of decimal digits.
+    pub fn new(
+        kind: u8,
+        poster: Address,
+        block_number: u64,
+        timestamp: u64,
+        request_id: Option<B256>,
+        base_fee_l1: Option<U256>,
+    ) -> Self {
+        Self {
+            kind,
+            sender: alloc::format!("{poster:#x}"),
+            block_number,
+            timestamp,
+            request_id: request_id.map_or(Value::Null, |id| Value::String(id.to_string())),
+            base_fee_l1: base_fee_l1.map_or(Value::Null, |fee| {
+                u64::try_from(fee).map_or_else(|_| Value::String(fee.to_string()), Value::from)
+            }),
+        }
+    }
+}
+
 /// Normalized header data used by sequencer decoders.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct L1Header {
@@ -104,20 +367,17 @@ pub struct L1Header {
 }
 impl L1Header {
     /// Converts the raw JSON header plus delayed count into a normalized header.
-    pub fn from_header(header: &Header, delayed_messages_read: u64) -> Result<Self, String> {
+    pub fn from_header(
+        header: &Header,
+        delayed_messages_read: u64,
+    ) -> Result<Self, FeedDecodeError> {
         let poster = Address::from_str(&header.sender)
-            .map_err(|e| format!("failed to parse poster address: {e}"))?;
-        let request_id_str = header.request_id.as_str();
-        let request_id = match request_id_str {
-            Some(s) => {
-                let bytes = <[u8; 32]>::from_hex(s.trim_start_matches("0x"))
-                    .map_err(|e| format!("failed to parse request_id hex string '{s}': {e}"))?;
-                Some(FixedBytes::from(bytes))
-            }
-            None => None,
-        };
-        let base_fee_l1_u64 = header.base_fee_l1.as_u64();
-        let base_fee_l1 = base_fee_l1_u64.map(|x| U256::from(x));
+            .map_err(|_| FeedDecodeError::InvalidPoster(header.sender.clone()))?;
+        let request_id = value_to_u256(&header.request_id)
+            .ok_or_else(|| FeedDecodeError::InvalidRequestId(header.request_id.to_string()))?
+            .map(|id| FixedBytes::from(id.to_be_bytes::<32>()));
+        let base_fee_l1 = value_to_u256(&header.base_fee_l1)
+            .ok_or_else(|| FeedDecodeError::InvalidBaseFee(header.base_fee_l1.to_string()))?;
         Ok(Self {
             kind: header.kind,
             block_number: header.block_number,
@@ -188,3 +448,143 @@ impl MessageType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    fn header(request_id: &str, base_fee_l1: &str) -> Result<L1Header, FeedDecodeError> {
+        let json = format!(
+            r#"{{"kind":9,"sender":"0x1111111111111111111111111111111111111111","blockNumber":1,"timestamp":2,"requestId":{request_id},"baseFeeL1":{base_fee_l1}}}"#
+        );
+        let header: Header = serde_json::from_str(&json).unwrap();
+        L1Header::from_header(&header, 0)
+    }
+
+    #[test]
+    fn header_fields_accept_every_nitro_shape() {
+        let id = b256!("0x00000000000000000000000000000000000000000000000000000000000a4b05");
+        let hex_id = r#""0x00000000000000000000000000000000000000000000000000000000000a4b05""#;
+
+        let parsed = header(hex_id, "null").unwrap();
+        assert_eq!(parsed.request_id, Some(id));
+        assert_eq!(parsed.base_fee_l1, None);
+
+        let parsed = header("674565", r#""0x3b9aca00""#).unwrap();
+        assert_eq!(parsed.request_id, Some(id));
+        assert_eq!(parsed.base_fee_l1, Some(U256::from(1_000_000_000u64)));
+
+        let built = Header::new(
+            9,
+            Address::repeat_byte(0x11),
+            1,
+            2,
+            Some(id),
+            Some(U256::from(7)),
+        );
+        assert_eq!(built.sender, "0x1111111111111111111111111111111111111111");
+        let parsed = L1Header::from_header(&built, 0).unwrap();
+        assert_eq!(parsed.request_id, Some(id));
+        assert_eq!(parsed.base_fee_l1, Some(U256::from(7)));
+
+        let wide = (U256::from(1) << 128) + U256::from(1);
+        let parsed = header("null", "340282366920938463463374607431768211457").unwrap();
+        assert_eq!(parsed.request_id, None);
+        assert_eq!(parsed.base_fee_l1, Some(wide));
+
+        let built = Header::new(9, Address::ZERO, 1, 2, None, Some(wide));
+        let json = serde_json::to_string(&built).unwrap();
+        let parsed: Header = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            L1Header::from_header(&parsed, 0).unwrap().base_fee_l1,
+            Some(wide)
+        );
+    }
+
+    #[test]
+    fn header_deserializes_from_value() {
+        let json = serde_json::json!({
+            "kind": 9,
+            "sender": "0x1111111111111111111111111111111111111111",
+            "blockNumber": 1,
+            "timestamp": 2,
+            "requestId": "0x00000000000000000000000000000000000000000000000000000000000a4b05",
+            "baseFeeL1": 1_000_000_000u64,
+        });
+        let header: Header = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&header).unwrap(), json);
+        let parsed = L1Header::from_header(&header, 0).unwrap();
+        assert_eq!(
+            parsed.request_id,
+            Some(FixedBytes::from(U256::from(0xa4b05)))
+        );
+        assert_eq!(parsed.base_fee_l1, Some(U256::from(1_000_000_000u64)));
+    }
+
+    #[test]
+    fn header_rejects_malformed_fields() {
+        assert_eq!(
+            header("-1", "null"),
+            Err(FeedDecodeError::InvalidRequestId("-1".into()))
+        );
+        assert_eq!(
+            header("null", "1.5"),
+            Err(FeedDecodeError::InvalidBaseFee("1.5".into()))
+        );
+        assert_eq!(
+            header(r#""0xzz""#, "null"),
+            Err(FeedDecodeError::InvalidRequestId(r#""0xzz""#.into()))
+        );
+    }
+
+    #[test]
+    fn block_metadata_reports_timeboosted_txs() {
+        let json = r#"{"sequenceNumber":9,"message":{"message":{"header":{"kind":3,"sender":"0x0000000000000000000000000000000000000000","blockNumber":1,"timestamp":1,"requestId":null,"baseFeeL1":null},"l2Msg":""},"delayedMessagesRead":1},"blockHash":"0x1111111111111111111111111111111111111111111111111111111111111111","signature":null,"blockMetadata":"AAUB"}"#;
+        let msg: BroadcastFeedMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(msg.block_hash, Some(B256::repeat_byte(0x11)));
+
+        let metadata = msg.block_metadata.clone().unwrap();
+        assert_eq!(metadata.0[..], [0x00, 0x05, 0x01]);
+        assert_eq!(metadata.timeboosted_txs().unwrap(), [0, 2, 8]);
+        assert!(metadata.is_tx_timeboosted(2).unwrap());
+        assert!(!metadata.is_tx_timeboosted(3).unwrap());
+        assert!(!metadata.is_tx_timeboosted(100).unwrap());
+        let reencoded = serde_json::to_string(&msg).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BroadcastFeedMessage>(&reencoded).unwrap(),
+            msg
+        );
+
+        assert_eq!(
+            BlockMetadata::default().is_tx_timeboosted(0),
+            Err(FeedDecodeError::EmptyBlockMetadata)
+        );
+        assert_eq!(
+            BlockMetadata(vec![1, 0xff].into()).timeboosted_txs(),
+            Err(FeedDecodeError::UnknownBlockMetadataVersion(1))
+        );
+    }
+
+    #[test]
+    fn root_carries_confirmed_sequence_number() {
+        let root: Root = serde_json::from_str(
+            r#"{"version":1,"confirmedSequenceNumberMessage":{"sequenceNumber":42}}"#,
+        )
+        .unwrap();
+        assert_eq!(root.messages, None);
+        assert_eq!(
+            root.confirmed_sequence_number_message,
+            Some(ConfirmedSequenceNumberMessage {
+                sequence_number: 42
+            })
+        );
+    }
+
+    #[test]
+    fn raw_json_round_trips_large_numbers() {
+        let json = r#"{"kind":3,"sender":"0x0000000000000000000000000000000000000000","blockNumber":1,"timestamp":1,"requestId":null,"baseFeeL1":340282366920938463463374607431768211457}"#;
+        let header: Header = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&header).unwrap(), json);
+    }
+}
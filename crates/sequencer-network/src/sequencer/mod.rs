@@ -1,2 +1,18 @@
+/// Merging of several feed sources into one ordered stream.
+#[cfg(feature = "client")]
+pub mod aggregator;
+/// On-disk feed archive and paced replayer.
+#[cfg(feature = "archive")]
+pub mod archive;
+/// Reconnecting WebSocket client for the sequencer feed.
+#[cfg(feature = "client")]
+pub mod client;
+/// Feed stream events and sequence-number tracking.
+pub mod event;
 /// Types for Arbitrum sequencer broadcast feed payloads.
 pub mod feed;
+/// WebSocket broadcaster and relay for the sequencer feed.
+#[cfg(feature = "server")]
+pub mod server;
+/// Feed message hashing and sequencer signature verification.
+pub mod signature;
@@ -0,0 +1,529 @@
+//! WebSocket broadcaster for the Nitro sequencer feed.
+//!
+//! Serves [`BroadcastFeedMessage`]s as Nitro-compatible [`Root`] frames. It can re-serve an
+//! upstream feed through [`FeedServerHandle::relay`] or stand in for a sequencer in tests.
+//!
+//! Nitro reference: `broadcaster/broadcaster.go`, `broadcaster/backlog/backlog.go`.
+
+use alloc::{collections::VecDeque, sync::Arc, vec, vec::Vec};
+use core::time::Duration;
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Mutex, MutexGuard},
+};
+
+use futures_core::Stream;
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::broadcast,
+    time::{Instant, interval_at},
+};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        self, Message, Utf8Bytes,
+        handshake::server::{ErrorResponse, Request, Response},
+        http::StatusCode,
+    },
+};
+
+use crate::sequencer::{
+    client::{HEADER_CHAIN_ID, HEADER_REQUESTED_SEQUENCE_NUMBER},
+    event::FeedEvent,
+    feed::{BroadcastFeedMessage, ConfirmedSequenceNumberMessage, Root},
+};
+
+/// Feed schema version written into every [`Root`] frame.
+pub const FEED_SERVER_VERSION: u8 = 1;
+
+/// Settings for [`FeedServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedServerConfig {
+    /// Chain id served; clients announcing a different `Arbitrum-Chain-Id` are rejected.
+    pub chain_id: Option<u64>,
+    /// Maximum number of messages kept for clients that resume from an older sequence number.
+    pub backlog_capacity: usize,
+    /// Number of frames a client may fall behind before it is disconnected.
+    pub client_queue_capacity: usize,
+    /// Interval between Ping frames, which keep quiet connections under client idle timeouts.
+    pub ping_interval: Duration,
+}
+
+impl FeedServerConfig {
+    /// Creates a config with Nitro-like defaults.
+    pub const fn new() -> Self {
+        Self {
+            chain_id: None,
+            backlog_capacity: 100_000,
+            client_queue_capacity: 4096,
+            ping_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Sets the chain id the server accepts clients for.
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the number of messages retained for catch-up.
+    pub const fn with_backlog_capacity(mut self, capacity: usize) -> Self {
+        self.backlog_capacity = capacity;
+        self
+    }
+
+    /// Sets how many frames a slow client may lag behind before it is dropped.
+    pub const fn with_client_queue_capacity(mut self, capacity: usize) -> Self {
+        self.client_queue_capacity = capacity;
+        self
+    }
+
+    /// Sets the interval between Ping frames sent to every client.
+    pub const fn with_ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = interval;
+        self
+    }
+}
+
+impl Default for FeedServerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bounded, contiguous run of feed messages ordered by sequence number.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedBacklog {
+    messages: VecDeque<BroadcastFeedMessage>,
+    capacity: usize,
+}
+
+impl FeedBacklog {
+    /// Creates an empty backlog holding at most `capacity` messages.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the number of retained messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if no messages are retained.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Returns the oldest retained sequence number.
+    pub fn first_sequence_number(&self) -> Option<u64> {
+        self.messages.front().map(|msg| msg.sequence_number)
+    }
+
+    /// Returns the newest retained sequence number.
+    pub fn last_sequence_number(&self) -> Option<u64> {
+        self.messages.back().map(|msg| msg.sequence_number)
+    }
+
+    /// Appends `msg`, evicting the oldest message when full.
+    ///
+    /// Returns `false` and keeps the backlog unchanged for an already seen sequence number. A
+    /// message that skips ahead restarts the backlog, so it always stays contiguous.
+    pub fn push(&mut self, msg: BroadcastFeedMessage) -> bool {
+        if let Some(last) = self.last_sequence_number() {
+            if msg.sequence_number <= last {
+                return false;
+            }
+            if msg.sequence_number != last + 1 {
+                self.messages.clear();
+            }
+        }
+        if self.capacity == 0 {
+            return true;
+        }
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(msg);
+        true
+    }
+
+    /// Drops every message up to and including the confirmed `sequence_number`.
+    pub fn confirm(&mut self, sequence_number: u64) {
+        while self
+            .first_sequence_number()
+            .is_some_and(|first| first <= sequence_number)
+        {
+            self.messages.pop_front();
+        }
+    }
+
+    /// Returns the retained messages starting at `sequence_number`, or all of them for `None`.
+    pub fn messages_from(&self, sequence_number: Option<u64>) -> Vec<BroadcastFeedMessage> {
+        let skip = match (sequence_number, self.first_sequence_number()) {
+            (Some(start), Some(first)) => usize::try_from(start.saturating_sub(first))
+                .unwrap_or(usize::MAX)
+                .min(self.messages.len()),
+            _ => 0,
+        };
+        self.messages.iter().skip(skip).cloned().collect()
+    }
+}
+
+struct Shared {
+    config: FeedServerConfig,
+    backlog: Mutex<FeedBacklog>,
+    frames: broadcast::Sender<Utf8Bytes>,
+}
+
+impl Shared {
+    fn backlog(&self) -> MutexGuard<'_, FeedBacklog> {
+        self.backlog
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Listening feed server. Run it with [`FeedServer::run`] and feed it through a
+/// [`FeedServerHandle`].
+pub struct FeedServer {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+impl core::fmt::Debug for FeedServer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeedServer")
+            .field("listener", &self.listener)
+            .field("config", &self.shared.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FeedServer {
+    /// Binds the server to `addr`.
+    pub async fn bind(addr: impl ToSocketAddrs, config: FeedServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let (frames, _) = broadcast::channel(config.client_queue_capacity.max(1));
+        let shared = Arc::new(Shared {
+            backlog: Mutex::new(FeedBacklog::new(config.backlog_capacity)),
+            config,
+            frames,
+        });
+        Ok(Self { listener, shared })
+    }
+
+    /// Returns the bound address.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Returns a handle for publishing messages to connected clients.
+    pub fn handle(&self) -> FeedServerHandle {
+        FeedServerHandle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Accepts clients until the listener fails, serving each one on its own task.
+    pub async fn run(self) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept().await?;
+            tokio::spawn(serve(stream, self.shared.clone()));
+        }
+    }
+}
+
+/// Cloneable handle that publishes to a [`FeedServer`].
+#[derive(Clone)]
+pub struct FeedServerHandle {
+    shared: Arc<Shared>,
+}
+
+impl core::fmt::Debug for FeedServerHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeedServerHandle")
+            .field("clients", &self.client_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FeedServerHandle {
+    /// Adds `msg` to the backlog and sends it to every connected client.
+    ///
+    /// Returns `false` if the sequence number was already broadcast.
+    pub fn broadcast(&self, msg: BroadcastFeedMessage) -> bool {
+        let frame = encode(Root {
+            version: FEED_SERVER_VERSION,
+            messages: Some(vec![msg.clone()]),
+            confirmed_sequence_number_message: None,
+        });
+        let mut backlog = self.shared.backlog();
+        if !backlog.push(msg) {
+            return false;
+        }
+        // Sending under the backlog lock keeps new clients from missing or repeating a frame.
+        let _ = self.shared.frames.send(frame);
+        true
+    }
+
+    /// Trims confirmed messages from the backlog and announces the confirmation to clients.
+    pub fn confirm(&self, sequence_number: u64) {
+        let frame = encode(Root {
+            version: FEED_SERVER_VERSION,
+            messages: None,
+            confirmed_sequence_number_message: Some(ConfirmedSequenceNumberMessage {
+                sequence_number,
+            }),
+        });
+        let mut backlog = self.shared.backlog();
+        backlog.confirm(sequence_number);
+        let _ = self.shared.frames.send(frame);
+    }
+
+    /// Returns a snapshot of the backlog.
+    pub fn backlog(&self) -> FeedBacklog {
+        self.shared.backlog().clone()
+    }
+
+    /// Returns the number of connected clients.
+    pub fn client_count(&self) -> usize {
+        self.shared.frames.receiver_count()
+    }
+
+    /// Re-serves an upstream feed until `events` ends.
+    ///
+    /// Messages and confirmations are forwarded; gap, duplicate and disconnect events are
+    /// ignored.
+    pub async fn relay<S>(&self, mut events: S)
+    where
+        S: Stream<Item = FeedEvent> + Unpin,
+    {
+        while let Some(event) = events.next().await {
+            match event {
+                FeedEvent::Message(msg) => {
+                    self.broadcast(msg);
+                }
+                FeedEvent::ConfirmedSequenceNumber { sequence_number } => {
+                    self.confirm(sequence_number);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn encode(root: Root) -> Utf8Bytes {
+    serde_json::to_string(&root)
+        .expect("feed frames serialize to JSON")
+        .into()
+}
+
+fn header_u64(req: &Request, name: &str) -> Option<u64> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+/// Serves one client: catch-up from the backlog, then live frames until either side hangs up.
+async fn serve(stream: TcpStream, shared: Arc<Shared>) -> Result<(), tungstenite::Error> {
+    let mut requested = None;
+    let ws = accept_hdr_async(stream, |req: &Request, resp: Response| {
+        requested = header_u64(req, HEADER_REQUESTED_SEQUENCE_NUMBER);
+        match (shared.config.chain_id, header_u64(req, HEADER_CHAIN_ID)) {
+            (Some(expected), Some(announced)) if expected != announced => {
+                let mut err = ErrorResponse::new(Some(format!(
+                    "feed serves chain {expected}, client requested {announced}"
+                )));
+                *err.status_mut() = StatusCode::BAD_REQUEST;
+                Err(err)
+            }
+            _ => Ok(resp),
+        }
+    })
+    .await?;
+
+    let (catch_up, mut frames) = {
+        let backlog = shared.backlog();
+        (backlog.messages_from(requested), shared.frames.subscribe())
+    };
+    let (mut sink, mut incoming) = ws.split();
+    if !catch_up.is_empty() {
+        let root = Root {
+            version: FEED_SERVER_VERSION,
+            messages: Some(catch_up),
+            confirmed_sequence_number_message: None,
+        };
+        sink.send(Message::text(encode(root))).await?;
+    }
+
+    // Nitro's broadcaster pings every client on a fixed interval; quiet feeds would otherwise
+    // trip the client idle timeout.
+    let period = shared.config.ping_interval.max(Duration::from_millis(1));
+    let mut ping = interval_at(Instant::now() + period, period);
+    loop {
+        tokio::select! {
+            _ = ping.tick() => sink.send(Message::Ping(Default::default())).await?,
+            frame = frames.recv() => match frame {
+                Ok(frame) => sink.send(Message::text(frame)).await?,
+                // A lagging client would silently miss messages; drop it so it resumes cleanly.
+                Err(_) => return sink.close().await,
+            },
+            msg = incoming.next() => match msg {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::{sleep, timeout};
+
+    use super::*;
+    use crate::sequencer::client::{FeedClient, FeedClientConfig, FeedSubscription};
+
+    fn msg(sequence_number: u64) -> BroadcastFeedMessage {
+        BroadcastFeedMessage {
+            sequence_number,
+            ..Default::default()
+        }
+    }
+
+    fn seqs(messages: &[BroadcastFeedMessage]) -> Vec<u64> {
+        messages.iter().map(|msg| msg.sequence_number).collect()
+    }
+
+    async fn next(events: &mut FeedSubscription) -> FeedEvent {
+        timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn backlog_is_bounded_and_contiguous() {
+        let mut backlog = FeedBacklog::new(3);
+        for seq in 1..=4 {
+            assert!(backlog.push(msg(seq)));
+        }
+        assert!(!backlog.push(msg(3)));
+        assert_eq!(seqs(&backlog.messages_from(None)), [2, 3, 4]);
+        assert_eq!(seqs(&backlog.messages_from(Some(3))), [3, 4]);
+        assert_eq!(seqs(&backlog.messages_from(Some(1))), [2, 3, 4]);
+        assert!(backlog.messages_from(Some(9)).is_empty());
+
+        backlog.confirm(2);
+        assert_eq!(backlog.first_sequence_number(), Some(3));
+
+        assert!(backlog.push(msg(10)));
+        assert_eq!(seqs(&backlog.messages_from(None)), [10]);
+    }
+
+    #[tokio::test]
+    async fn server_resumes_clients_and_broadcasts_live_frames() {
+        let server = FeedServer::bind("127.0.0.1:0", FeedServerConfig::new().with_chain_id(7))
+            .await
+            .unwrap();
+        let url = format!("ws://{}", server.local_addr().unwrap());
+        let handle = server.handle();
+        tokio::spawn(server.run());
+        for seq in 1..=3 {
+            handle.broadcast(msg(seq));
+        }
+
+        let config = FeedClientConfig::new(url)
+            .with_chain_id(7)
+            .with_start_sequence_number(2);
+        let mut events = FeedClient::from_config(config).subscribe().unwrap();
+        assert_eq!(
+            next(&mut events)
+                .await
+                .as_message()
+                .map(|m| m.sequence_number),
+            Some(2)
+        );
+        assert_eq!(
+            next(&mut events)
+                .await
+                .as_message()
+                .map(|m| m.sequence_number),
+            Some(3)
+        );
+
+        while handle.client_count() == 0 {
+            sleep(Duration::from_millis(5)).await;
+        }
+        handle.broadcast(msg(4));
+        handle.confirm(3);
+        assert_eq!(
+            next(&mut events)
+                .await
+                .as_message()
+                .map(|m| m.sequence_number),
+            Some(4)
+        );
+        assert_eq!(
+            next(&mut events).await,
+            FeedEvent::ConfirmedSequenceNumber { sequence_number: 3 }
+        );
+        assert_eq!(seqs(&handle.backlog().messages_from(None)), [4]);
+    }
+
+    #[tokio::test]
+    async fn pings_keep_idle_clients_connected() {
+        let config = FeedServerConfig::new().with_ping_interval(Duration::from_millis(50));
+        let server = FeedServer::bind("127.0.0.1:0", config).await.unwrap();
+        let url = format!("ws://{}", server.local_addr().unwrap());
+        let handle = server.handle();
+        tokio::spawn(server.run());
+
+        let config = FeedClientConfig::new(url).with_idle_timeout(Duration::from_millis(200));
+        let mut events = FeedClient::from_config(config).subscribe().unwrap();
+        while handle.client_count() == 0 {
+            sleep(Duration::from_millis(5)).await;
+        }
+        assert!(
+            timeout(Duration::from_millis(600), events.next())
+                .await
+                .is_err()
+        );
+
+        handle.broadcast(msg(1));
+        assert_eq!(
+            next(&mut events)
+                .await
+                .as_message()
+                .map(|m| m.sequence_number),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn server_rejects_other_chains() {
+        let server = FeedServer::bind("127.0.0.1:0", FeedServerConfig::new().with_chain_id(7))
+            .await
+            .unwrap();
+        let url = format!("ws://{}", server.local_addr().unwrap());
+        tokio::spawn(server.run());
+
+        let config = FeedClientConfig::new(url)
+            .with_chain_id(8)
+            .with_max_reconnect_attempts(0);
+        let events: Vec<FeedEvent> = FeedClient::from_config(config)
+            .subscribe()
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(
+            events.as_slice(),
+            [FeedEvent::Disconnected { retry_in: None, .. }]
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+//! Feed message hashing and sequencer signature verification.
+//!
+//! Nitro reference:
+//! - `arbos/arbostypes/messagewithmeta.go` -> `MessageWithMetadata.Hash`
+//! - `broadcaster/message/message.go` -> `BroadcastFeedMessage.Hash`
+//! - `util/signature/verifier.go` -> `Verifier.VerifyHash`
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use alloy_primitives::{Address, B256, Signature, SignatureError, keccak256};
+use alloy_rlp::{BufMut, Encodable};
+
+use crate::sequencer::feed::{
+    BroadcastFeedMessage, FeedDecodeError, L1Header, L1IncomingMessage, MessageWithMetadata,
+};
+
+/// Domain prefix hashed in front of every signed feed message.
+pub const FEED_MESSAGE_HASH_PREFIX: &[u8] = b"Arbitrum Nitro Feed:";
+
+/// Error while checking the sequencer signature of a feed message.
+#[derive(Debug)]
+pub enum FeedSignatureError {
+    /// The message carries no signature.
+    MissingSignature,
+    /// The message could not be normalized for hashing.
+    Decode(FeedDecodeError),
+    /// The signature is malformed or no key can be recovered from it.
+    InvalidSignature(SignatureError),
+    /// The signature was produced by an unexpected key.
+    UnexpectedSigner {
+        /// Signer the caller trusts.
+        expected: Address,
+        /// Signer recovered from the signature.
+        recovered: Address,
+    },
+}
+
+impl fmt::Display for FeedSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSignature => write!(f, "feed message is not signed"),
+            Self::Decode(err) => write!(f, "failed to hash feed message: {err}"),
+            Self::InvalidSignature(err) => write!(f, "invalid feed message signature: {err}"),
+            Self::UnexpectedSigner {
+                expected,
+                recovered,
+            } => {
+                write!(f, "feed message signed by {recovered}, expected {expected}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FeedSignatureError {}
+
+impl From<FeedDecodeError> for FeedSignatureError {
+    fn from(err: FeedDecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<SignatureError> for FeedSignatureError {
+    fn from(err: SignatureError) -> Self {
+        Self::InvalidSignature(err)
+    }
+}
+
+impl L1IncomingMessage {
+    /// RLP-encodes the message the way Nitro serializes `L1IncomingMessage`.
+    ///
+    /// The batch gas cost and batch data stats are trailing optional fields: they are omitted
+    /// when unset, and an unset gas cost is encoded as empty when only the stats are present.
+    pub fn rlp_bytes(&self) -> Result<Vec<u8>, FeedDecodeError> {
+        let header = L1Header::from_header(&self.header, 0)?;
+        let l2msg = self.l2msg_bytes()?;
+
+        let mut header_payload = Vec::new();
+        header.kind.encode(&mut header_payload);
+        header.poster.encode(&mut header_payload);
+        header.block_number.encode(&mut header_payload);
+        header.timestamp.encode(&mut header_payload);
+        match header.request_id {
+            Some(id) => id.encode(&mut header_payload),
+            None => [0u8; 0][..].encode(&mut header_payload),
+        }
+        header
+            .base_fee_l1
+            .unwrap_or_default()
+            .encode(&mut header_payload);
+
+        let mut payload = Vec::new();
+        encode_list(&header_payload, &mut payload);
+        l2msg[..].encode(&mut payload);
+        if self.legacy_batch_gas_cost.is_some() || self.batch_data_stats.is_some() {
+            self.legacy_batch_gas_cost
+                .unwrap_or_default()
+                .encode(&mut payload);
+        }
+        if let Some(stats) = &self.batch_data_stats {
+            let mut stats_payload = Vec::new();
+            stats.length.encode(&mut stats_payload);
+            stats.non_zeros.encode(&mut stats_payload);
+            encode_list(&stats_payload, &mut payload);
+        }
+
+        let mut out = Vec::new();
+        encode_list(&payload, &mut out);
+        Ok(out)
+    }
+}
+
+impl MessageWithMetadata {
+    /// Returns the hash the sequencer signs for this message at `sequence_number`.
+    ///
+    /// `keccak256(prefix ‖ u64 sequence number ‖ u64 chain id ‖ u64 delayed messages read ‖
+    /// rlp(message))`, with big-endian integers.
+    pub fn hash(&self, sequence_number: u64, chain_id: u64) -> Result<B256, FeedDecodeError> {
+        let message = self.l1_incoming_message.rlp_bytes()?;
+        let mut preimage = Vec::with_capacity(FEED_MESSAGE_HASH_PREFIX.len() + 24 + message.len());
+        preimage.extend_from_slice(FEED_MESSAGE_HASH_PREFIX);
+        preimage.extend_from_slice(&sequence_number.to_be_bytes());
+        preimage.extend_from_slice(&chain_id.to_be_bytes());
+        preimage.extend_from_slice(&self.delayed_messages_read.to_be_bytes());
+        preimage.extend_from_slice(&message);
+        Ok(keccak256(preimage))
+    }
+}
+
+impl BroadcastFeedMessage {
+    /// Returns the hash the sequencer signs for this message.
+    pub fn hash(&self, chain_id: u64) -> Result<B256, FeedDecodeError> {
+        self.message_with_meta_data
+            .hash(self.sequence_number, chain_id)
+    }
+
+    /// Recovers the address that signed this message.
+    ///
+    /// The signature is a 65-byte `r ‖ s ‖ v` over [`Self::hash`], without any message prefix.
+    pub fn recover_signer(&self, chain_id: u64) -> Result<Address, FeedSignatureError> {
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(FeedSignatureError::MissingSignature)?;
+        let signature = Signature::from_raw(signature)?;
+        Ok(signature.recover_address_from_prehash(&self.hash(chain_id)?)?)
+    }
+
+    /// Checks that this message was signed by `expected_signer`.
+    pub fn verify_signature(
+        &self,
+        chain_id: u64,
+        expected_signer: Address,
+    ) -> Result<(), FeedSignatureError> {
+        let recovered = self.recover_signer(chain_id)?;
+        if recovered != expected_signer {
+            return Err(FeedSignatureError::UnexpectedSigner {
+                expected: expected_signer,
+                recovered,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn encode_list(payload: &[u8], out: &mut dyn BufMut) {
+    alloy_rlp::Header {
+        list: true,
+        payload_length: payload.len(),
+    }
+    .encode(out);
+    out.put_slice(payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequencer::feed::{BatchDataStats, Header};
+    use alloy_primitives::{Bytes, address, hex};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use serde_json::{Value, json};
+
+    fn message() -> BroadcastFeedMessage {
+        BroadcastFeedMessage {
+            sequence_number: 7,
+            message_with_meta_data: MessageWithMetadata {
+                l1_incoming_message: L1IncomingMessage {
+                    header: Header {
+                        kind: 3,
+                        sender: "0xa4b000000000000000000073657175656e636572".into(),
+                        block_number: 0x10,
+                        timestamp: 0x20,
+                        request_id: Value::Null,
+                        base_fee_l1: Value::Null,
+                    },
+                    l2msg: "BAE=".into(),
+                    legacy_batch_gas_cost: None,
+                    batch_data_stats: None,
+                },
+                delayed_messages_read: 1,
+            },
+            block_hash: None,
+            signature: None,
+            block_metadata: None,
+        }
+    }
+
+    fn sign(msg: &mut BroadcastFeedMessage, signer: &PrivateKeySigner, chain_id: u64) {
+        let signature = signer.sign_hash_sync(&msg.hash(chain_id).unwrap()).unwrap();
+        let mut raw = signature.as_bytes();
+        // Nitro signs with `crypto.Sign`, which uses a 0/1 recovery id.
+        raw[64] -= 27;
+        msg.signature = Some(Bytes::copy_from_slice(&raw));
+    }
+
+    #[test]
+    fn message_rlp_matches_nitro_layout() {
+        let mut msg = message().message_with_meta_data.l1_incoming_message;
+        assert_eq!(
+            msg.rlp_bytes().unwrap(),
+            hex!("deda0394a4b000000000000000000073657175656e636572102080808204" "01")
+        );
+
+        msg.header.request_id = json!(format!("0x{}", "11".repeat(32)));
+        msg.header.base_fee_l1 = json!(1_000_000_000u64);
+        msg.batch_data_stats = Some(BatchDataStats {
+            length: 2,
+            non_zeros: 1,
+        });
+        assert_eq!(
+            msg.rlp_bytes().unwrap(),
+            hex!(
+                "f847"
+                "f83e0394a4b000000000000000000073657175656e6365721020"
+                "a01111111111111111111111111111111111111111111111111111111111111111"
+                "843b9aca00"
+                "820401"
+                "80"
+                "c20201"
+            )
+        );
+    }
+
+    #[test]
+    fn verify_signature_checks_signer_and_domain() {
+        let signer = PrivateKeySigner::random();
+        let mut msg = message();
+        assert!(matches!(
+            msg.verify_signature(42161, signer.address()),
+            Err(FeedSignatureError::MissingSignature)
+        ));
+
+        sign(&mut msg, &signer, 42161);
+        msg.verify_signature(42161, signer.address()).unwrap();
+
+        let other = address!("0x0000000000000000000000000000000000000001");
+        assert!(matches!(
+            msg.verify_signature(42161, other),
+            Err(FeedSignatureError::UnexpectedSigner { recovered, .. }) if recovered == signer.address()
+        ));
+        // The chain id and sequence number are part of the signed hash.
+        assert!(msg.verify_signature(421614, signer.address()).is_err());
+        msg.sequence_number += 1;
+        assert!(msg.verify_signature(42161, signer.address()).is_err());
+    }
+
+    #[test]
+    fn signature_round_trips_as_base64() {
+        let mut msg = message();
+        msg.signature = Some(Bytes::from_static(&[1, 2, 3]));
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["signature"], "AQID");
+
+        let text = serde_json::to_string(&msg).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BroadcastFeedMessage>(&text).unwrap(),
+            msg
+        );
+        assert_eq!(
+            serde_json::to_value(message()).unwrap()["signature"],
+            serde_json::Value::Null
+        );
+    }
+}